@@ -0,0 +1,66 @@
+//! Compares the worklist flood fill against the recursive cascade it replaced, on an
+//! open board with a single mine in the far corner.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use minesweeper_core::{CellContent, CellState, GameBoard};
+
+fn open_board(size: usize) -> GameBoard {
+    let mut board = GameBoard::from_layout(size, size, &[(size - 1, size - 1)]).unwrap();
+    board.start_game();
+    board
+}
+
+/// The cascade as it used to be: recursive, with a full-board win scan per cell.
+fn legacy_reveal(board: &mut GameBoard, x: usize, y: usize) {
+    if !board.board[y][x].state.is_revealable() {
+        return;
+    }
+    board.board[y][x].state = CellState::Revealed;
+    if board.board[y][x].content == CellContent::Empty {
+        for ny in y.saturating_sub(1)..=(y + 1).min(board.height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(board.width - 1) {
+                legacy_reveal(board, nx, ny);
+            }
+        }
+    }
+    let unrevealed = board
+        .board
+        .iter()
+        .flatten()
+        .filter(|c| c.state.is_revealable() && c.content != CellContent::Mine)
+        .count();
+    if unrevealed == 0 {
+        board.game_won = true;
+    }
+}
+
+fn flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("open 150x150");
+    group.sample_size(10);
+    group.bench_function("recursive", |b| {
+        b.iter_batched(
+            || open_board(150),
+            |mut board| legacy_reveal(&mut board, 0, 0),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("worklist", |b| {
+        b.iter_batched(
+            || open_board(150),
+            |mut board| board.reveal_cell(0, 0),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    c.bench_function("open 1000x1000 worklist", |b| {
+        b.iter_batched(
+            || open_board(1000),
+            |mut board| board.reveal_cell(0, 0),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, flood_fill);
+criterion_main!(benches);
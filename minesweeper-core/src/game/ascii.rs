@@ -0,0 +1,238 @@
+//! Compact text grids for test fixtures and bug reports, one character per cell:
+//!
+//! ```text
+//! # lost on the mine at (2, 1)
+//! 1F21.
+//! 12!2.
+//! 0112*
+//! ```
+//!
+//! | char  | cell                                   |
+//! |-------|----------------------------------------|
+//! | `.`   | hidden, safe                           |
+//! | `*`   | hidden mine                            |
+//! | `F`   | flagged mine                           |
+//! | `f`   | flag on a safe cell                    |
+//! | `Q`   | question mark on a mine                |
+//! | `q`   | question mark on a safe cell           |
+//! | `0`-`8` | revealed, with that many adjacent mines |
+//! | `!`   | revealed mine (the one that was hit)   |
+//!
+//! Spaces are ignored, as are blank lines and lines starting with `#`. Revealed
+//! numbers must match the mines around them.
+
+use super::{Cell, CellContent, CellState, GameBoard, LayoutError};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsciiBoardError {
+    Empty,
+    InvalidChar {
+        x: usize,
+        y: usize,
+        ch: char,
+    },
+    Layout(LayoutError),
+    WrongNumber {
+        x: usize,
+        y: usize,
+        expected: u8,
+        found: u8,
+    },
+}
+
+impl fmt::Display for AsciiBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiBoardError::Empty => write!(f, "board has no rows"),
+            AsciiBoardError::InvalidChar { x, y, ch } => {
+                write!(f, "unexpected '{}' at ({}, {})", ch, x, y)
+            }
+            AsciiBoardError::Layout(e) => write!(f, "{}", e),
+            AsciiBoardError::WrongNumber {
+                x,
+                y,
+                expected,
+                found,
+            } => write!(
+                f,
+                "cell ({}, {}) shows {} but has {} adjacent mines",
+                x, y, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AsciiBoardError {}
+
+impl GameBoard {
+    /// Parses a board in the ASCII grid format. The mine count is the number of mines
+    /// drawn. A board with a revealed mine is lost and one with every safe cell revealed
+    /// is won; otherwise call `start_game` before playing.
+    pub fn from_ascii(text: &str) -> Result<Self, AsciiBoardError> {
+        let mut cells = Vec::new();
+        let mut numbers = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let y = cells.len();
+            let row = line
+                .chars()
+                .filter(|ch| *ch != ' ')
+                .enumerate()
+                .map(|(x, ch)| {
+                    if let '0'..='8' = ch {
+                        numbers.push((x, y, ch as u8 - b'0'));
+                    }
+                    decode_cell(ch).ok_or(AsciiBoardError::InvalidChar { x, y, ch })
+                })
+                .collect::<Result<Vec<Cell>, _>>()?;
+            cells.push(row);
+        }
+        if cells.is_empty() {
+            return Err(AsciiBoardError::Empty);
+        }
+
+        let mine_count = cells
+            .iter()
+            .flatten()
+            .filter(|cell| cell.content == CellContent::Mine)
+            .count();
+        let board = GameBoard::from_cells(cells, mine_count).map_err(AsciiBoardError::Layout)?;
+
+        for (x, y, found) in numbers {
+            let expected = match board.board[y][x].content {
+                CellContent::Number(n) => n,
+                _ => 0,
+            };
+            if expected != found {
+                return Err(AsciiBoardError::WrongNumber {
+                    x,
+                    y,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(board)
+    }
+
+    /// The board in the ASCII grid format, one line per row.
+    pub fn to_ascii(&self) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in &self.board {
+            text.extend(row.iter().map(|cell| encode_cell(*cell)));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+fn encode_cell(cell: Cell) -> char {
+    let mine = cell.content == CellContent::Mine;
+    match (cell.state, mine) {
+        (CellState::Hidden, false) => '.',
+        (CellState::Hidden, true) => '*',
+        (CellState::Flagged, false) => 'f',
+        (CellState::Flagged, true) => 'F',
+        (CellState::Questioned, false) => 'q',
+        (CellState::Questioned, true) => 'Q',
+        (CellState::Revealed, true) => '!',
+        (CellState::Revealed, false) => match cell.content {
+            CellContent::Number(n) => char::from_digit(n as u32, 10).unwrap_or('?'),
+            _ => '0',
+        },
+    }
+}
+
+fn decode_cell(ch: char) -> Option<Cell> {
+    let (state, content) = match ch {
+        '.' => (CellState::Hidden, CellContent::Empty),
+        '*' => (CellState::Hidden, CellContent::Mine),
+        'f' => (CellState::Flagged, CellContent::Empty),
+        'F' => (CellState::Flagged, CellContent::Mine),
+        'q' => (CellState::Questioned, CellContent::Empty),
+        'Q' => (CellState::Questioned, CellContent::Mine),
+        '!' => (CellState::Revealed, CellContent::Mine),
+        '0'..='8' => (CellState::Revealed, CellContent::Empty),
+        _ => return None,
+    };
+    Some(Cell { state, content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "1F21.\n12!2.\n0112*\n";
+        let board = GameBoard::from_ascii(text).unwrap();
+
+        assert_eq!(board.to_ascii(), text);
+        assert_eq!((board.width, board.height, board.mine_count), (5, 3, 3));
+        assert_eq!(board.flags_placed(), 1);
+        assert_eq!(board.get_cell_state(2, 1), CellState::Revealed);
+    }
+
+    #[test]
+    fn test_ignores_spaces_and_comments() {
+        let board = GameBoard::from_ascii("# corner mine\n\n * 1 .\n 1 1 .\n . . .\n").unwrap();
+
+        assert_eq!(board.to_ascii(), "*1.\n11.\n...\n");
+        assert!(!board.first_click);
+        assert!(!board.game_over);
+    }
+
+    #[test]
+    fn test_status_from_revealed_cells() {
+        let lost = GameBoard::from_ascii("!1\n11\n").unwrap();
+        assert!(lost.game_over && !lost.game_won);
+
+        let won = GameBoard::from_ascii("F1\n11\n").unwrap();
+        assert!(won.game_over && won.game_won);
+
+        let playing = GameBoard::from_ascii("*1\n1.\n").unwrap();
+        assert!(!playing.game_over);
+    }
+
+    #[test]
+    fn test_loaded_board_plays_on() {
+        let mut board = GameBoard::from_ascii("*1.\n11.\n...\n").unwrap();
+        board.start_game();
+
+        assert!(board.reveal_cell(2, 2));
+        assert!(board.game_won);
+        assert_eq!(board.to_ascii(), "*10\n110\n000\n");
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert_eq!(
+            GameBoard::from_ascii("# nothing\n").unwrap_err(),
+            AsciiBoardError::Empty
+        );
+        assert_eq!(
+            GameBoard::from_ascii("..\n.x\n").unwrap_err(),
+            AsciiBoardError::InvalidChar {
+                x: 1,
+                y: 1,
+                ch: 'x'
+            }
+        );
+        assert!(matches!(
+            GameBoard::from_ascii("...\n..\n").unwrap_err(),
+            AsciiBoardError::Layout(LayoutError::RowLength { y: 1, .. })
+        ));
+        assert_eq!(
+            GameBoard::from_ascii("*2\n..\n").unwrap_err(),
+            AsciiBoardError::WrongNumber {
+                x: 1,
+                y: 0,
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,1734 @@
+use super::cell::{Cell, CellContent, CellState};
+use super::history::{BoardStatus, CellChange, Move, MoveHistory, MoveKind};
+use super::no_guess::{is_solvable_within, NO_GUESS_WORK_BUDGET};
+use super::placement::PlacementPolicy;
+use super::stats::Clicks;
+use crate::debug::{is_logging_enabled, is_show_mines_enabled};
+use log::{debug, error, warn};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Layouts tried for a no-guess board before settling for one that may need guessing. A
+/// count rather than a time limit, so a seed gives the same board on any machine. Large
+/// boards run out of `NO_GUESS_WORK_BUDGET` first.
+const NO_GUESS_MAX_ATTEMPTS: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutError {
+    OutOfBounds {
+        x: usize,
+        y: usize,
+    },
+    DuplicateMine {
+        x: usize,
+        y: usize,
+    },
+    RowLength {
+        y: usize,
+        expected: usize,
+        found: usize,
+    },
+    MineCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::OutOfBounds { x, y } => {
+                write!(f, "mine at ({}, {}) is off the board", x, y)
+            }
+            LayoutError::DuplicateMine { x, y } => {
+                write!(f, "mine at ({}, {}) is listed more than once", x, y)
+            }
+            LayoutError::RowLength { y, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", y, found, expected)
+            }
+            LayoutError::MineCountMismatch { expected, found } => {
+                write!(f, "board has {} mines, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// With the `serde` feature, boards (de)serialize through [`BoardFile`](super::board_file::BoardFile),
+/// so every deserialized board has been validated.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "super::board_file::BoardFile",
+        into = "super::board_file::BoardFile"
+    )
+)]
+pub struct GameBoard {
+    pub board: Vec<Vec<Cell>>,
+    pub width: usize,
+    pub height: usize,
+    pub mine_count: usize,
+    pub game_over: bool,
+    pub game_won: bool,
+    pub first_click: bool,
+    pub game_started: bool,
+    pub seed: u64,
+    pub no_guess: bool,
+    pub placement: PlacementPolicy,
+    pub question_marks: bool,
+    /// Set once undo, redo or another aid has been used; assisted games don't count toward
+    /// best times.
+    pub assisted: bool,
+    /// Hints shown during this game. Each one also marks the game as assisted.
+    pub hints_used: usize,
+    /// Clicks made through `GameManager`, for efficiency stats.
+    pub clicks: Clicks,
+    /// 3BV of the current layout, updated whenever the numbers are.
+    pub(super) layout_three_bv: usize,
+    flags_placed: usize,
+    safe_cells_remaining: usize,
+    history: MoveHistory,
+    journal: Vec<(usize, usize, Cell)>,
+    layout_snapshot: Option<Vec<Vec<Cell>>>,
+}
+
+impl GameBoard {
+    pub fn new(width: usize, height: usize, mine_count: usize) -> Self {
+        Self::with_seed(width, height, mine_count, rand::thread_rng().gen())
+    }
+
+    /// Creates a board whose mine layout is fully determined by `seed` and the first click.
+    /// A mine count larger than the board is cut down to fill every cell.
+    pub fn with_seed(width: usize, height: usize, mine_count: usize, seed: u64) -> Self {
+        let mine_count = if mine_count > width * height {
+            if is_logging_enabled() {
+                warn!(
+                    "{} mines don't fit on a {}x{} board, using {}",
+                    mine_count,
+                    width,
+                    height,
+                    width * height
+                );
+            }
+            width * height
+        } else {
+            mine_count
+        };
+        if is_logging_enabled() {
+            debug!(
+                "Creating new GameBoard: {}x{} with {} mines, seed {}",
+                width, height, mine_count, seed
+            );
+        }
+
+        let board = vec![vec![Cell::default(); width]; height];
+
+        Self {
+            board,
+            width,
+            height,
+            mine_count,
+            game_over: false,
+            game_won: false,
+            first_click: true,
+            game_started: false,
+            seed,
+            no_guess: false,
+            placement: PlacementPolicy::default(),
+            question_marks: false,
+            assisted: false,
+            hints_used: 0,
+            clicks: Clicks::default(),
+            layout_three_bv: 0,
+            flags_placed: 0,
+            safe_cells_remaining: (width * height).saturating_sub(mine_count),
+            history: MoveHistory::new(),
+            journal: Vec::new(),
+            layout_snapshot: None,
+        }
+    }
+
+    /// Builds a board with mines at exactly the given positions. Mines are already placed,
+    /// so the first click is treated like any other; call `start_game` before playing.
+    pub fn from_layout(
+        width: usize,
+        height: usize,
+        mines: &[(usize, usize)],
+    ) -> Result<Self, LayoutError> {
+        let mut board = Self::with_seed(width, height, mines.len(), 0);
+        board.set_layout(mines)?;
+        Ok(board)
+    }
+
+    /// Replaces the mines with exactly the given positions, keeping cell states. Meant
+    /// for boards where nothing is revealed yet, such as a replay whose first reveal was
+    /// undone and then made somewhere else.
+    pub fn set_layout(&mut self, mines: &[(usize, usize)]) -> Result<(), LayoutError> {
+        if mines.len() != self.mine_count {
+            return Err(LayoutError::MineCountMismatch {
+                expected: self.mine_count,
+                found: mines.len(),
+            });
+        }
+        for cell in self.board.iter_mut().flatten() {
+            cell.content = CellContent::Empty;
+        }
+
+        for &(x, y) in mines {
+            if x >= self.width || y >= self.height {
+                return Err(LayoutError::OutOfBounds { x, y });
+            }
+            if matches!(self.board[y][x].content, CellContent::Mine) {
+                return Err(LayoutError::DuplicateMine { x, y });
+            }
+            self.board[y][x].content = CellContent::Mine;
+        }
+
+        self.calculate_numbers();
+        self.first_click = false;
+        Ok(())
+    }
+
+    /// Rebuilds a board from saved cells, keeping their states. Numbers and counters are
+    /// recomputed from the mines; a board with no mines and nothing revealed is treated
+    /// as not yet clicked. A board with a revealed mine comes back lost, and one with
+    /// every safe cell revealed comes back won. Move history starts empty.
+    pub fn from_cells(cells: Vec<Vec<Cell>>, mine_count: usize) -> Result<Self, LayoutError> {
+        let height = cells.len();
+        let width = cells.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = cells.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(LayoutError::RowLength {
+                y,
+                expected: width,
+                found: row.len(),
+            });
+        }
+
+        let mut board = Self::with_seed(width, height, mine_count, 0);
+        board.board = cells;
+        let found = board.count_total_mines();
+        let revealed = board
+            .board
+            .iter()
+            .flatten()
+            .any(|cell| cell.state == CellState::Revealed);
+        if found != 0 || revealed {
+            if found != mine_count {
+                return Err(LayoutError::MineCountMismatch {
+                    expected: mine_count,
+                    found,
+                });
+            }
+            board.calculate_numbers();
+            board.first_click = false;
+
+            let mine_revealed =
+                board.board.iter().flatten().any(|cell| {
+                    cell.state == CellState::Revealed && cell.content == CellContent::Mine
+                });
+            board.game_won = !mine_revealed && board.safe_cells_remaining == 0;
+            board.game_over = mine_revealed || board.game_won;
+        }
+        board.flags_placed = board
+            .board
+            .iter()
+            .flatten()
+            .filter(|cell| cell.state == CellState::Flagged)
+            .count();
+        Ok(board)
+    }
+
+    /// Positions of all mines, row by row.
+    pub fn mine_positions(&self) -> Vec<(usize, usize)> {
+        let mut positions = Vec::new();
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if matches!(cell.content, CellContent::Mine) {
+                    positions.push((x, y));
+                }
+            }
+        }
+        positions
+    }
+
+    pub fn place_mines(&mut self, exclude_x: usize, exclude_y: usize) {
+        if is_logging_enabled() {
+            debug!(
+                "Placing {} mines, excluding position ({}, {})",
+                self.mine_count, exclude_x, exclude_y
+            );
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+
+        if self.no_guess {
+            self.place_no_guess_mines(&mut rng, exclude_x, exclude_y);
+        } else {
+            let excluded = self.excluded_cells(self.placement, exclude_x, exclude_y);
+            self.scatter_mines(&mut rng, &excluded);
+            self.calculate_numbers();
+        }
+    }
+
+    fn excluded_cells(&self, policy: PlacementPolicy, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let total = self.width * self.height;
+        // The opening is the cell and its neighbours: fewer at edges and corners
+        let mut opening = self.neighbors(x, y);
+        opening.push((x, y));
+
+        match policy {
+            PlacementPolicy::SafeOpening if self.mine_count + opening.len() <= total => opening,
+            PlacementPolicy::SafeOpening | PlacementPolicy::SafeCell if self.mine_count < total => {
+                vec![(x, y)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn place_no_guess_mines(&mut self, rng: &mut ChaCha8Rng, exclude_x: usize, exclude_y: usize) {
+        self.place_no_guess_mines_within(rng, exclude_x, exclude_y, NO_GUESS_WORK_BUDGET);
+    }
+
+    /// Tries layouts until one is solvable without guessing, or `budget` (see
+    /// `NO_GUESS_WORK_BUDGET`) or the attempt cap runs out.
+    fn place_no_guess_mines_within(
+        &mut self,
+        rng: &mut ChaCha8Rng,
+        exclude_x: usize,
+        exclude_y: usize,
+        mut budget: u64,
+    ) {
+        // No-guess boards always start from an opening, whatever the placement policy
+        let excluded = self.excluded_cells(PlacementPolicy::SafeOpening, exclude_x, exclude_y);
+
+        let cells = (self.width * self.height) as u64;
+        let mut attempts = 0;
+        while attempts < NO_GUESS_MAX_ATTEMPTS {
+            // Placing the mines and numbering the board is one pass over it
+            let Some(left) = budget.checked_sub(cells) else {
+                break;
+            };
+            budget = left;
+            attempts += 1;
+            for row in &mut self.board {
+                for cell in row {
+                    cell.content = CellContent::Empty;
+                }
+            }
+            self.scatter_mines(rng, &excluded);
+            self.calculate_numbers();
+
+            match is_solvable_within(self, exclude_x, exclude_y, &mut budget) {
+                Some(true) => {
+                    if is_logging_enabled() {
+                        debug!("Found no-guess layout after {} attempts", attempts);
+                    }
+                    return;
+                }
+                Some(false) => {}
+                None => break,
+            }
+        }
+
+        // Keep the last layout, but stop promising that it can be cleared by logic
+        if is_logging_enabled() {
+            warn!(
+                "No no-guess layout in {} attempts, using one that may need guessing",
+                attempts
+            );
+        }
+        self.no_guess = false;
+    }
+
+    fn scatter_mines(&mut self, rng: &mut ChaCha8Rng, excluded: &[(usize, usize)]) {
+        // Without room for every mine the loop below would never finish, so drop the
+        // exclusions and, for a mine count changed after creation, cap it
+        let cells = self.width * self.height;
+        let excluded = if self.mine_count + excluded.len() > cells {
+            &[]
+        } else {
+            excluded
+        };
+        let target = self.mine_count.min(cells);
+        let mut mines_placed = 0;
+
+        while mines_placed < target {
+            let x = rng.gen_range(0..self.width);
+            let y = rng.gen_range(0..self.height);
+
+            if excluded.contains(&(x, y)) || matches!(self.board[y][x].content, CellContent::Mine) {
+                continue;
+            }
+
+            self.board[y][x].content = CellContent::Mine;
+            mines_placed += 1;
+        }
+
+        if is_logging_enabled() {
+            debug!("Successfully placed {} mines", mines_placed);
+        }
+    }
+
+    pub(super) fn calculate_numbers(&mut self) {
+        self.safe_cells_remaining = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if matches!(self.board[y][x].content, CellContent::Mine) {
+                    continue;
+                }
+
+                if self.board[y][x].state != CellState::Revealed {
+                    self.safe_cells_remaining += 1;
+                }
+
+                let mine_count = self.count_adjacent_mines(x, y);
+                if mine_count > 0 {
+                    self.board[y][x].content = CellContent::Number(mine_count);
+                } else {
+                    self.board[y][x].content = CellContent::Empty;
+                }
+            }
+        }
+
+        self.layout_three_bv = self.count_three_bv(false);
+    }
+
+    fn count_adjacent_mines(&self, x: usize, y: usize) -> u8 {
+        let mut count = 0;
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+
+                if nx >= 0
+                    && nx < self.width as i32
+                    && ny >= 0
+                    && ny < self.height as i32
+                    && matches!(
+                        self.board[ny as usize][nx as usize].content,
+                        CellContent::Mine
+                    )
+                {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    pub fn reveal_cell(&mut self, x: usize, y: usize) -> bool {
+        let before = self.begin_move();
+        let revealed = self.reveal(x, y);
+        self.end_move(MoveKind::Reveal, x, y, before);
+        revealed
+    }
+
+    fn reveal(&mut self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height || !self.game_started {
+            if is_logging_enabled() {
+                debug!(
+                    "Cannot reveal cell ({}, {}): out of bounds or game not started",
+                    x, y
+                );
+            }
+            return false;
+        }
+
+        let cell_state = self.board[y][x].state;
+
+        match cell_state {
+            CellState::Hidden | CellState::Questioned => {
+                if is_logging_enabled() {
+                    debug!("Revealing cell ({}, {})", x, y);
+                }
+
+                if self.first_click {
+                    if is_logging_enabled() {
+                        debug!("First click detected, placing mines");
+                    }
+                    self.place_mines(x, y);
+                    self.first_click = false;
+                }
+
+                if matches!(self.board[y][x].content, CellContent::Mine) {
+                    if is_logging_enabled() {
+                        error!("Mine hit at ({}, {}) - game over!", x, y);
+                    }
+                    self.set_state(x, y, CellState::Revealed);
+                    self.game_over = true;
+                    self.reveal_all_mines();
+                    return true;
+                }
+
+                self.flood_reveal(x, y);
+                self.check_win_condition();
+                true
+            }
+            _ => {
+                if is_logging_enabled() {
+                    debug!(
+                        "Cannot reveal cell ({}, {}): already revealed or flagged",
+                        x, y
+                    );
+                }
+                false
+            }
+        }
+    }
+
+    /// Reveals a safe cell and, if it is empty, the whole connected opening around it.
+    /// Uses an explicit worklist so huge openings can't overflow the stack.
+    fn flood_reveal(&mut self, x: usize, y: usize) {
+        let mut worklist = vec![(x, y)];
+        let mut revealed = 0;
+
+        while let Some((cx, cy)) = worklist.pop() {
+            if !self.board[cy][cx].state.is_revealable() {
+                continue;
+            }
+
+            self.set_state(cx, cy, CellState::Revealed);
+            self.safe_cells_remaining -= 1;
+            revealed += 1;
+
+            if matches!(self.board[cy][cx].content, CellContent::Empty) {
+                for (nx, ny) in self.neighbors(cx, cy) {
+                    if self.board[ny][nx].state.is_revealable() {
+                        worklist.push((nx, ny));
+                    }
+                }
+            }
+        }
+
+        if is_logging_enabled() && revealed > 1 {
+            debug!("Opening from ({}, {}) revealed {} cells", x, y, revealed);
+        }
+    }
+
+    pub fn chord(&mut self, x: usize, y: usize) -> bool {
+        let before = self.begin_move();
+        let revealed = self.chord_neighbors(x, y);
+        self.end_move(MoveKind::Chord, x, y, before);
+        revealed
+    }
+
+    fn chord_neighbors(&mut self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height || self.game_over || !self.game_started {
+            if is_logging_enabled() {
+                debug!(
+                    "Cannot chord cell ({}, {}): out of bounds, game over or game not started",
+                    x, y
+                );
+            }
+            return false;
+        }
+
+        let cell = &self.board[y][x];
+        let number = match (cell.state, cell.content) {
+            (CellState::Revealed, CellContent::Number(n)) => n as usize,
+            _ => return false,
+        };
+
+        let neighbors = self.neighbors(x, y);
+        let flagged = neighbors
+            .iter()
+            .filter(|&&(nx, ny)| matches!(self.board[ny][nx].state, CellState::Flagged))
+            .count();
+
+        if flagged != number {
+            if is_logging_enabled() {
+                debug!(
+                    "Cannot chord cell ({}, {}): {} flags around a {}",
+                    x, y, flagged, number
+                );
+            }
+            return false;
+        }
+
+        if is_logging_enabled() {
+            debug!("Chording cell ({}, {})", x, y);
+        }
+
+        let mut revealed_any = false;
+        for (nx, ny) in neighbors {
+            if self.game_over {
+                break;
+            }
+            if self.board[ny][nx].state.is_revealable() {
+                revealed_any |= self.reveal(nx, ny);
+            }
+        }
+
+        revealed_any
+    }
+
+    pub(crate) fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbors = Vec::with_capacity(8);
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+
+                if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
+                    neighbors.push((nx as usize, ny as usize));
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn reveal_all_mines(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = self.board[y][x];
+                // Correct flags stay in place, as in the classic game
+                if matches!(cell.content, CellContent::Mine)
+                    && !matches!(cell.state, CellState::Flagged | CellState::Revealed)
+                {
+                    self.set_state(x, y, CellState::Revealed);
+                }
+            }
+        }
+    }
+
+    fn check_win_condition(&mut self) {
+        if self.safe_cells_remaining == 0 {
+            if is_logging_enabled() {
+                debug!("Win condition met! All non-mine cells revealed.");
+            }
+            self.game_won = true;
+            self.game_over = true;
+        }
+    }
+
+    pub fn toggle_flag(&mut self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height || self.game_over || !self.game_started {
+            return false;
+        }
+
+        let next_state = match self.board[y][x].state {
+            CellState::Hidden => CellState::Flagged,
+            CellState::Flagged if self.question_marks => CellState::Questioned,
+            CellState::Flagged => CellState::Hidden,
+            CellState::Questioned => CellState::Hidden,
+            CellState::Revealed => return false,
+        };
+
+        let before = self.begin_move();
+        match next_state {
+            CellState::Flagged => self.flags_placed += 1,
+            _ if matches!(self.board[y][x].state, CellState::Flagged) => self.flags_placed -= 1,
+            _ => {}
+        }
+        self.set_state(x, y, next_state);
+        self.end_move(MoveKind::Flag, x, y, before);
+        true
+    }
+
+    pub fn flags_placed(&self) -> usize {
+        self.flags_placed
+    }
+
+    /// Mines left to flag; goes negative when more flags than mines are placed.
+    pub fn mines_remaining(&self) -> isize {
+        self.mine_count as isize - self.flags_placed as isize
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(mv) = self.history.take_undo() else {
+            return false;
+        };
+
+        if is_logging_enabled() {
+            debug!("Undoing {:?} at ({}, {})", mv.kind, mv.x, mv.y);
+        }
+
+        for change in mv.changes.iter().rev() {
+            self.board[change.y][change.x] = change.before;
+        }
+        self.restore_status(mv.before);
+        self.assisted = true;
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(mv) = self.history.take_redo() else {
+            return false;
+        };
+
+        if is_logging_enabled() {
+            debug!("Redoing {:?} at ({}, {})", mv.kind, mv.x, mv.y);
+        }
+
+        for change in &mv.changes {
+            self.board[change.y][change.x] = change.after;
+        }
+        self.restore_status(mv.after);
+        self.assisted = true;
+        true
+    }
+
+    pub fn history(&self) -> &MoveHistory {
+        &self.history
+    }
+
+    fn status(&self) -> BoardStatus {
+        BoardStatus {
+            game_over: self.game_over,
+            game_won: self.game_won,
+            first_click: self.first_click,
+            flags_placed: self.flags_placed,
+            safe_cells_remaining: self.safe_cells_remaining,
+        }
+    }
+
+    fn restore_status(&mut self, status: BoardStatus) {
+        self.game_over = status.game_over;
+        self.game_won = status.game_won;
+        self.first_click = status.first_click;
+        self.flags_placed = status.flags_placed;
+        self.safe_cells_remaining = status.safe_cells_remaining;
+    }
+
+    fn set_state(&mut self, x: usize, y: usize, state: CellState) {
+        self.journal.push((x, y, self.board[y][x]));
+        self.board[y][x].state = state;
+    }
+
+    /// Starts recording the cells a move changes. The first click also places the mines,
+    /// so the whole board is captured before it.
+    fn begin_move(&mut self) -> BoardStatus {
+        self.journal.clear();
+        self.layout_snapshot = self.first_click.then(|| self.board.clone());
+        self.status()
+    }
+
+    /// Stores everything changed since `begin_move` as a single undoable step.
+    fn end_move(&mut self, kind: MoveKind, x: usize, y: usize, before: BoardStatus) {
+        let mut changes = Vec::new();
+
+        if let Some(snapshot) = self.layout_snapshot.take() {
+            for (cy, row) in snapshot.into_iter().enumerate() {
+                for (cx, old) in row.into_iter().enumerate() {
+                    if old != self.board[cy][cx] {
+                        changes.push(CellChange {
+                            x: cx,
+                            y: cy,
+                            before: old,
+                            after: self.board[cy][cx],
+                        });
+                    }
+                }
+            }
+            self.journal.clear();
+        } else {
+            let mut seen = HashSet::new();
+            for (cx, cy, old) in self.journal.drain(..) {
+                if seen.insert((cx, cy)) && old != self.board[cy][cx] {
+                    changes.push(CellChange {
+                        x: cx,
+                        y: cy,
+                        before: old,
+                        after: self.board[cy][cx],
+                    });
+                }
+            }
+        }
+
+        let after = self.status();
+        if changes.is_empty() && before == after {
+            return;
+        }
+
+        self.history.push(Move {
+            kind,
+            x,
+            y,
+            changes,
+            before,
+            after,
+        });
+    }
+
+    pub fn reset(&mut self) {
+        self.board = vec![vec![Cell::default(); self.width]; self.height];
+        self.game_over = false;
+        self.game_won = false;
+        self.first_click = true;
+        self.game_started = false;
+        self.assisted = false;
+        self.hints_used = 0;
+        self.clicks = Clicks::default();
+        self.layout_three_bv = 0;
+        self.flags_placed = 0;
+        self.safe_cells_remaining = (self.width * self.height).saturating_sub(self.mine_count);
+        self.history.clear();
+        self.journal.clear();
+    }
+
+    pub fn start_game(&mut self) {
+        if is_logging_enabled() {
+            debug!("Starting game");
+        }
+        self.game_started = true;
+
+        // If show_mines flag is enabled, place mines immediately for debugging
+        if is_show_mines_enabled() && self.first_click {
+            if is_logging_enabled() {
+                debug!("Show mines flag enabled - placing mines immediately for debugging");
+            }
+            // Place mines excluding a seeded position to avoid immediate game over
+            let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+            let exclude_x = rng.gen_range(0..self.width);
+            let exclude_y = rng.gen_range(0..self.height);
+            self.place_mines(exclude_x, exclude_y);
+            self.first_click = false; // Mark as if first click already happened
+        }
+    }
+
+    pub fn get_cell_content(&self, x: usize, y: usize) -> CellContent {
+        self.board[y][x].content
+    }
+
+    pub fn get_cell_state(&self, x: usize, y: usize) -> CellState {
+        self.board[y][x].state
+    }
+
+    #[cfg(test)]
+    pub fn set_mine(&mut self, x: usize, y: usize) {
+        self.board[y][x].content = CellContent::Mine;
+    }
+
+    pub fn count_total_mines(&self) -> usize {
+        let mut count = 0;
+        for row in &self.board {
+            for cell in row {
+                if matches!(cell.content, CellContent::Mine) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::no_guess::is_solvable_without_guessing;
+
+    #[test]
+    fn test_board_initialization() {
+        let board = GameBoard::new(10, 8, 15);
+
+        assert_eq!(board.width, 10);
+        assert_eq!(board.height, 8);
+        assert_eq!(board.mine_count, 15);
+        assert!(!board.game_over);
+        assert!(!board.game_won);
+        assert!(board.first_click);
+        assert!(!board.game_started);
+
+        assert_eq!(board.board.len(), 8);
+        assert_eq!(board.board[0].len(), 10);
+        for row in &board.board {
+            for cell in row {
+                assert_eq!(cell.content, CellContent::Empty);
+                assert_eq!(cell.state, CellState::Hidden);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flag_before_first_click_does_not_count_as_revealed() {
+        let mut board = GameBoard::with_seed(3, 3, 1, 0);
+        board.placement = PlacementPolicy::SafeCell;
+        board.start_game();
+        board.toggle_flag(2, 2);
+        board.reveal_cell(0, 0);
+
+        let (mx, my) = (0..9)
+            .map(|i| (i % 3, i / 3))
+            .find(|&(x, y)| board.get_cell_content(x, y) == CellContent::Mine)
+            .unwrap();
+        for (x, y) in board.neighbors(1, 1).into_iter().chain([(1, 1)]) {
+            if (x, y) != (mx, my) && (x, y) != (2, 2) {
+                board.reveal_cell(x, y);
+            }
+        }
+        assert_eq!(board.game_won, (mx, my) == (2, 2));
+    }
+
+    #[test]
+    fn test_from_layout() {
+        let mut board = GameBoard::from_layout(3, 3, &[(0, 0), (2, 2)]).unwrap();
+
+        assert_eq!(board.mine_count, 2);
+        assert!(!board.first_click);
+        assert_eq!(board.get_cell_content(1, 1), CellContent::Number(2));
+        assert_eq!(board.mine_positions(), vec![(0, 0), (2, 2)]);
+
+        board.start_game();
+        board.reveal_cell(2, 0);
+        assert_eq!(board.count_total_mines(), 2);
+        assert_eq!(board.get_cell_state(2, 0), CellState::Revealed);
+    }
+
+    #[test]
+    fn test_from_layout_rejects_bad_positions() {
+        assert_eq!(
+            GameBoard::from_layout(3, 3, &[(3, 0)]).unwrap_err(),
+            LayoutError::OutOfBounds { x: 3, y: 0 }
+        );
+        assert_eq!(
+            GameBoard::from_layout(3, 3, &[(1, 1), (1, 1)]).unwrap_err(),
+            LayoutError::DuplicateMine { x: 1, y: 1 }
+        );
+    }
+
+    #[test]
+    fn test_from_cells_keeps_states_and_counts() {
+        let mut original = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        original.start_game();
+        original.toggle_flag(0, 0);
+        original.toggle_flag(2, 2);
+        original.reveal_cell(1, 0);
+
+        let mut restored = GameBoard::from_cells(original.board.clone(), 1).unwrap();
+        restored.start_game();
+
+        assert_eq!(restored.board, original.board);
+        assert_eq!((restored.width, restored.height), (3, 3));
+        assert!(!restored.first_click);
+        assert_eq!(restored.flags_placed(), 2);
+
+        // The flagged safe cell still has to be revealed before the game is won
+        restored.reveal_cell(0, 2);
+        assert!(!restored.game_won);
+        restored.toggle_flag(2, 2);
+        restored.reveal_cell(2, 2);
+        assert!(restored.game_won);
+    }
+
+    #[test]
+    fn test_from_cells_without_mines_waits_for_first_click() {
+        let board = GameBoard::from_cells(vec![vec![Cell::default(); 5]; 5], 3).unwrap();
+        assert!(board.first_click);
+        assert_eq!(board.mine_count, 3);
+
+        // Revealed cells mean the mines were placed, so a board without any is wrong
+        let mut cells = vec![vec![Cell::default(); 5]; 5];
+        cells[2][2].state = CellState::Revealed;
+        assert_eq!(
+            GameBoard::from_cells(cells.clone(), 3).unwrap_err(),
+            LayoutError::MineCountMismatch {
+                expected: 3,
+                found: 0
+            }
+        );
+        let board = GameBoard::from_cells(cells, 0).unwrap();
+        assert!(!board.first_click);
+        assert_eq!(board.safe_cells_remaining, 24);
+    }
+
+    #[test]
+    fn test_from_cells_restores_finished_games() {
+        let mut lost = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        lost.board[0][0].state = CellState::Revealed;
+        let lost = GameBoard::from_cells(lost.board, 1).unwrap();
+        assert!(lost.game_over);
+        assert!(!lost.game_won);
+
+        let mut won = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        for cell in won.board.iter_mut().flatten().skip(1) {
+            cell.state = CellState::Revealed;
+        }
+        let won = GameBoard::from_cells(won.board, 1).unwrap();
+        assert!(won.game_over);
+        assert!(won.game_won);
+        assert_eq!(won.safe_cells_remaining, 0);
+    }
+
+    #[test]
+    fn test_from_cells_rejects_bad_shapes_and_counts() {
+        let mut ragged = vec![vec![Cell::default(); 3]; 3];
+        ragged[1].pop();
+        assert_eq!(
+            GameBoard::from_cells(ragged, 1).unwrap_err(),
+            LayoutError::RowLength {
+                y: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+
+        let board = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        assert_eq!(
+            GameBoard::from_cells(board.board, 2).unwrap_err(),
+            LayoutError::MineCountMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_mine_placement_excludes_first_click() {
+        let mut board = GameBoard::new(5, 5, 5);
+        board.start_game();
+
+        board.place_mines(2, 2);
+
+        assert_ne!(board.get_cell_content(2, 2), CellContent::Mine);
+        assert_eq!(board.count_total_mines(), 5);
+    }
+
+    #[test]
+    fn test_same_seed_same_layout() {
+        let mut first = GameBoard::with_seed(16, 16, 40, 42);
+        let mut second = GameBoard::with_seed(16, 16, 40, 42);
+        first.start_game();
+        second.start_game();
+
+        first.reveal_cell(3, 7);
+        second.reveal_cell(3, 7);
+
+        for y in 0..16 {
+            for x in 0..16 {
+                assert_eq!(first.get_cell_content(x, y), second.get_cell_content(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_different_seed_different_layout() {
+        let mut first = GameBoard::with_seed(16, 16, 40, 1);
+        let mut second = GameBoard::with_seed(16, 16, 40, 2);
+
+        first.place_mines(0, 0);
+        second.place_mines(0, 0);
+
+        let differs = (0..16).any(|y| {
+            (0..16).any(|x| first.get_cell_content(x, y) != second.get_cell_content(x, y))
+        });
+        assert!(differs);
+        assert_eq!(first.seed, 1);
+        assert_eq!(second.seed, 2);
+    }
+
+    #[test]
+    fn test_safe_opening_keeps_neighbors_clear() {
+        let mut board = GameBoard::with_seed(9, 9, 70, 3);
+        board.start_game();
+
+        board.reveal_cell(4, 4);
+
+        assert_eq!(board.count_total_mines(), 70);
+        assert_eq!(board.get_cell_content(4, 4), CellContent::Empty);
+        for (x, y) in board.neighbors(4, 4) {
+            assert_ne!(board.get_cell_content(x, y), CellContent::Mine);
+        }
+    }
+
+    #[test]
+    fn test_safe_opening_falls_back_on_dense_board() {
+        let mut board = GameBoard::with_seed(3, 3, 5, 3);
+
+        board.place_mines(1, 1);
+
+        assert_eq!(board.count_total_mines(), 5);
+        assert_ne!(board.get_cell_content(1, 1), CellContent::Mine);
+    }
+
+    #[test]
+    fn test_safe_opening_fits_in_a_corner_of_a_dense_board() {
+        // 4 free cells: too few for an opening in the middle, exactly a corner opening
+        let mut board = GameBoard::with_seed(5, 5, 21, 3);
+
+        board.place_mines(0, 0);
+
+        assert_eq!(board.count_total_mines(), 21);
+        assert_eq!(board.get_cell_content(0, 0), CellContent::Empty);
+    }
+
+    #[test]
+    fn test_safe_cell_policy_only_protects_clicked_cell() {
+        let mut board = GameBoard::with_seed(3, 3, 8, 3);
+        board.placement = PlacementPolicy::SafeCell;
+
+        board.place_mines(0, 0);
+
+        assert_eq!(board.count_total_mines(), 8);
+        assert_eq!(board.get_cell_content(0, 0), CellContent::Number(3));
+    }
+
+    #[test]
+    fn test_none_policy_fills_whole_board() {
+        let mut board = GameBoard::with_seed(2, 2, 4, 3);
+        board.placement = PlacementPolicy::None;
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+
+        assert_eq!(board.count_total_mines(), 4);
+        assert!(board.game_over);
+        assert!(!board.game_won);
+    }
+
+    #[test]
+    fn test_no_guess_layout_is_solvable() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 7);
+        board.no_guess = true;
+        board.start_game();
+
+        board.reveal_cell(4, 4);
+
+        assert_eq!(board.count_total_mines(), 10);
+        assert_eq!(board.get_cell_content(4, 4), CellContent::Empty);
+        assert!(is_solvable_without_guessing(&board, 4, 4));
+    }
+
+    #[test]
+    fn test_no_guess_dense_board_still_places_all_mines() {
+        let mut board = GameBoard::with_seed(3, 3, 8, 7);
+        board.no_guess = true;
+
+        board.place_mines(1, 1);
+
+        assert_eq!(board.count_total_mines(), 8);
+        assert_ne!(board.get_cell_content(1, 1), CellContent::Mine);
+    }
+
+    #[test]
+    fn test_no_guess_fallback_is_reported_and_reproducible() {
+        let place = || {
+            let mut board = GameBoard::with_seed(16, 16, 90, 3);
+            board.no_guess = true;
+            board.place_mines(8, 8);
+            board
+        };
+
+        let board = place();
+        assert!(!board.no_guess);
+        assert!(!is_solvable_without_guessing(&board, 8, 8));
+        assert_eq!(board.mine_positions(), place().mine_positions());
+    }
+
+    #[test]
+    fn test_mine_placement_never_overfills_the_board() {
+        let mut board = GameBoard::with_seed(3, 3, 20, 4);
+        assert_eq!(board.mine_count, 9);
+        board.place_mines(1, 1);
+        assert_eq!(board.mine_positions().len(), 9);
+
+        let mut board = GameBoard::with_seed(3, 3, 5, 4);
+        board.mine_count = 12;
+        board.place_mines(1, 1);
+        assert_eq!(board.mine_positions().len(), 9);
+    }
+
+    #[test]
+    fn test_no_guess_search_on_a_large_board_is_bounded() {
+        // The search gives up once its budget is spent, however long a solve would take.
+        // With the full budget this board falls back after about a second in release.
+        let mut board = GameBoard::with_seed(200, 200, 6000, 1);
+        board.no_guess = true;
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        board.place_no_guess_mines_within(&mut rng, 100, 100, 200_000);
+
+        assert!(!board.no_guess);
+        assert_eq!(board.mine_positions().len(), 6000);
+        assert_eq!(board.board[100][100].content, CellContent::Empty);
+    }
+
+    #[test]
+    fn test_number_calculation_simple_case() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             .*.
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        assert_eq!(board.get_cell_content(0, 0), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(0, 1), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(0, 2), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(1, 0), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(1, 2), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(2, 0), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(2, 1), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(2, 2), CellContent::Number(1));
+
+        assert_eq!(board.get_cell_content(1, 1), CellContent::Mine);
+    }
+
+    #[test]
+    fn test_number_calculation_corner_mine() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        assert_eq!(board.get_cell_content(0, 1), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(1, 0), CellContent::Number(1));
+        assert_eq!(board.get_cell_content(1, 1), CellContent::Number(1));
+
+        assert_eq!(board.get_cell_content(0, 2), CellContent::Empty);
+        assert_eq!(board.get_cell_content(1, 2), CellContent::Empty);
+        assert_eq!(board.get_cell_content(2, 0), CellContent::Empty);
+        assert_eq!(board.get_cell_content(2, 1), CellContent::Empty);
+        assert_eq!(board.get_cell_content(2, 2), CellContent::Empty);
+    }
+
+    #[test]
+    fn test_number_calculation_multiple_mines() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             .*.
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        assert_eq!(board.get_cell_content(0, 1), CellContent::Number(2));
+
+        assert_eq!(board.get_cell_content(1, 0), CellContent::Number(2));
+
+        assert_eq!(board.get_cell_content(2, 2), CellContent::Number(1));
+    }
+
+    #[test]
+    fn test_count_adjacent_mines() {
+        let board = GameBoard::from_ascii(
+            "*..
+             ...
+             ..*",
+        )
+        .unwrap();
+
+        assert_eq!(board.count_adjacent_mines(1, 1), 2);
+
+        assert_eq!(board.count_adjacent_mines(0, 2), 0);
+
+        assert_eq!(board.count_adjacent_mines(1, 0), 1);
+
+        assert_eq!(board.count_adjacent_mines(0, 1), 1);
+
+        assert_eq!(board.count_adjacent_mines(1, 2), 1);
+    }
+
+    #[test]
+    fn test_reveal_cell_basic() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        let result = board.reveal_cell(1, 1);
+        assert!(result);
+        assert_eq!(board.get_cell_state(1, 1), CellState::Revealed);
+        assert!(!board.first_click);
+    }
+
+    #[test]
+    fn test_reveal_cell_out_of_bounds() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        let result = board.reveal_cell(5, 5);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_reveal_cell_game_not_started() {
+        let mut board = GameBoard::new(3, 3, 1);
+        let result = board.reveal_cell(1, 1);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_reveal_cell_already_revealed() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        let result1 = board.reveal_cell(1, 1);
+        assert!(result1);
+
+        let result2 = board.reveal_cell(1, 1);
+        assert!(!result2);
+    }
+
+    #[test]
+    fn test_reveal_cell_cascade_empty() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+
+        for y in 0..3 {
+            for x in 0..3 {
+                if !(x == 2 && y == 2) {
+                    assert_eq!(board.get_cell_state(x, y), CellState::Revealed);
+                }
+            }
+        }
+
+        assert_eq!(board.get_cell_state(2, 2), CellState::Hidden);
+    }
+
+    #[test]
+    fn test_reveal_cell_cascade_huge_board() {
+        let mut board = GameBoard::new(400, 400, 1);
+        board.start_game();
+
+        board.set_mine(399, 399);
+        board.calculate_numbers();
+        board.first_click = false;
+
+        assert!(board.reveal_cell(0, 0));
+        assert!(board.game_won);
+        assert_eq!(board.history().moves().len(), 1);
+    }
+
+    #[test]
+    fn test_game_over_on_mine_hit() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             .*.
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        let result = board.reveal_cell(1, 1);
+        assert!(result);
+        assert!(board.game_over);
+        assert!(!board.game_won);
+
+        assert_eq!(board.get_cell_state(1, 1), CellState::Revealed);
+    }
+
+    #[test]
+    fn test_win_condition() {
+        let mut board = GameBoard::from_ascii(
+            "..
+             .*",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+        board.reveal_cell(0, 1);
+        board.reveal_cell(1, 0);
+
+        assert!(board.game_won);
+        assert!(board.game_over);
+    }
+
+    #[test]
+    fn test_chord_reveals_unflagged_neighbors() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+        board.toggle_flag(0, 0);
+
+        let result = board.chord(1, 1);
+        assert!(result);
+        assert!(board.game_won);
+        assert!(board.game_over);
+        assert_eq!(board.get_cell_state(0, 0), CellState::Flagged);
+    }
+
+    #[test]
+    fn test_chord_requires_matching_flag_count() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+
+        let result = board.chord(1, 1);
+        assert!(!result);
+        assert_eq!(board.get_cell_state(2, 2), CellState::Hidden);
+        assert!(!board.game_over);
+    }
+
+    #[test]
+    fn test_chord_with_wrong_flag_hits_mine() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ...",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+        board.toggle_flag(2, 2);
+
+        let result = board.chord(1, 1);
+        assert!(result);
+        assert!(board.game_over);
+        assert!(!board.game_won);
+        assert_eq!(board.get_cell_state(0, 0), CellState::Revealed);
+    }
+
+    #[test]
+    fn test_chord_on_hidden_or_empty_cell() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.start_game();
+
+        assert!(!board.chord(0, 0));
+
+        board.reveal_cell(0, 0);
+        assert!(!board.chord(0, 0));
+        assert!(!board.chord(5, 5));
+    }
+
+    #[test]
+    fn test_toggle_flag() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        let result1 = board.toggle_flag(1, 1);
+        assert!(result1);
+        assert_eq!(board.get_cell_state(1, 1), CellState::Flagged);
+
+        let result2 = board.toggle_flag(1, 1);
+        assert!(result2);
+        assert_eq!(board.get_cell_state(1, 1), CellState::Hidden);
+
+        board.reveal_cell(0, 0);
+        let result3 = board.toggle_flag(0, 0);
+        assert!(!result3);
+    }
+
+    #[test]
+    fn test_toggle_flag_cycles_question_mark() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.question_marks = true;
+        board.start_game();
+
+        assert!(board.toggle_flag(1, 1));
+        assert_eq!(board.get_cell_state(1, 1), CellState::Flagged);
+        assert_eq!(board.flags_placed(), 1);
+
+        assert!(board.toggle_flag(1, 1));
+        assert_eq!(board.get_cell_state(1, 1), CellState::Questioned);
+        assert_eq!(board.flags_placed(), 0);
+        assert_eq!(board.mines_remaining(), 1);
+
+        assert!(board.toggle_flag(1, 1));
+        assert_eq!(board.get_cell_state(1, 1), CellState::Hidden);
+    }
+
+    #[test]
+    fn test_questioned_cell_is_revealable() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.question_marks = true;
+        board.start_game();
+
+        board.toggle_flag(0, 0);
+        board.toggle_flag(0, 0);
+        assert_eq!(board.get_cell_state(0, 0), CellState::Questioned);
+
+        assert!(board.reveal_cell(0, 0));
+        assert_eq!(board.get_cell_state(0, 0), CellState::Revealed);
+        assert!(board.game_won);
+    }
+
+    #[test]
+    fn test_chord_ignores_question_marks() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ...",
+        )
+        .unwrap();
+        board.question_marks = true;
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+        board.toggle_flag(0, 0);
+        board.toggle_flag(0, 0);
+
+        assert!(!board.chord(1, 1));
+        assert!(!board.game_over);
+    }
+
+    #[test]
+    fn test_undo_redo_flag() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        board.toggle_flag(1, 1);
+        assert!(board.undo());
+        assert_eq!(board.get_cell_state(1, 1), CellState::Hidden);
+        assert_eq!(board.flags_placed(), 0);
+        assert!(board.assisted);
+
+        assert!(board.redo());
+        assert_eq!(board.get_cell_state(1, 1), CellState::Flagged);
+        assert_eq!(board.flags_placed(), 1);
+
+        assert!(!board.redo());
+    }
+
+    #[test]
+    fn test_undo_cascade_is_one_step() {
+        let mut board = GameBoard::from_ascii(
+            "...
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+        assert!(board.game_won);
+        assert_eq!(board.history().moves().len(), 1);
+
+        assert!(board.undo());
+        for y in 0..3 {
+            for x in 0..3 {
+                assert_eq!(board.get_cell_state(x, y), CellState::Hidden);
+            }
+        }
+        assert!(!board.game_won);
+        assert!(!board.game_over);
+        assert!(!board.undo());
+    }
+
+    #[test]
+    fn test_undo_mine_hit() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+        assert!(board.game_over);
+
+        assert!(board.undo());
+        assert!(!board.game_over);
+        assert_eq!(board.get_cell_state(0, 0), CellState::Hidden);
+        assert_eq!(board.get_cell_state(2, 2), CellState::Hidden);
+
+        assert!(board.redo());
+        assert!(board.game_over);
+        assert_eq!(board.get_cell_state(2, 2), CellState::Revealed);
+    }
+
+    #[test]
+    fn test_undo_first_click_clears_layout() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 5);
+        board.start_game();
+
+        board.reveal_cell(4, 4);
+        let layout: Vec<CellContent> = (0..81)
+            .map(|i| board.get_cell_content(i % 9, i / 9))
+            .collect();
+
+        assert!(board.undo());
+        assert!(board.first_click);
+        assert_eq!(board.count_total_mines(), 0);
+
+        assert!(board.redo());
+        assert!(!board.first_click);
+        for (i, content) in layout.into_iter().enumerate() {
+            assert_eq!(board.get_cell_content(i % 9, i / 9), content);
+        }
+    }
+
+    #[test]
+    fn test_new_move_after_undo_drops_redo() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        board.toggle_flag(0, 0);
+        board.undo();
+        board.toggle_flag(1, 1);
+
+        assert!(!board.redo());
+        assert_eq!(board.get_cell_state(0, 0), CellState::Hidden);
+    }
+
+    #[test]
+    fn test_failed_move_is_not_recorded() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        board.reveal_cell(5, 5);
+        board.chord(1, 1);
+
+        assert!(!board.history().can_undo());
+    }
+
+    #[test]
+    fn test_flag_accounting() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        assert_eq!(board.flags_placed(), 0);
+        assert_eq!(board.mines_remaining(), 1);
+
+        board.toggle_flag(0, 0);
+        board.toggle_flag(1, 0);
+        assert_eq!(board.flags_placed(), 2);
+        assert_eq!(board.mines_remaining(), -1);
+
+        board.toggle_flag(0, 0);
+        assert_eq!(board.flags_placed(), 1);
+        assert_eq!(board.mines_remaining(), 0);
+
+        board.reset();
+        assert_eq!(board.flags_placed(), 0);
+        assert_eq!(board.mines_remaining(), 1);
+    }
+
+    #[test]
+    fn test_flagged_mine_stays_flagged_on_game_over() {
+        let mut board = GameBoard::from_ascii(
+            "*..
+             ...
+             ..*",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.toggle_flag(0, 0);
+        board.reveal_cell(2, 2);
+
+        assert!(board.game_over);
+        assert_eq!(board.get_cell_state(0, 0), CellState::Flagged);
+        assert_eq!(board.flags_placed(), 1);
+    }
+
+    #[test]
+    fn test_toggle_flag_out_of_bounds() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        let result = board.toggle_flag(5, 5);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_toggle_flag_game_over() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        board.game_over = true;
+
+        let result = board.toggle_flag(1, 1);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut board = GameBoard::new(3, 3, 1);
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+        board.toggle_flag(0, 0);
+
+        board.reset();
+
+        assert!(!board.game_over);
+        assert!(!board.game_won);
+        assert!(board.first_click);
+        assert!(!board.game_started);
+
+        for row in &board.board {
+            for cell in row {
+                assert_eq!(cell.state, CellState::Hidden);
+                assert_eq!(cell.content, CellContent::Empty);
+            }
+        }
+    }
+
+    #[test]
+    fn test_start_game() {
+        let mut board = GameBoard::new(3, 3, 1);
+
+        assert!(!board.game_started);
+
+        board.start_game();
+
+        assert!(board.game_started);
+    }
+
+    #[test]
+    fn test_edge_case_single_cell_board() {
+        let mut board = GameBoard::new(1, 1, 0);
+        board.start_game();
+
+        board.reveal_cell(0, 0);
+
+        assert!(board.game_won);
+        assert!(board.game_over);
+    }
+
+    #[test]
+    fn test_edge_case_all_mines_except_one() {
+        let mut board = GameBoard::from_ascii(
+            "**
+             *.",
+        )
+        .unwrap();
+        board.start_game();
+
+        board.reveal_cell(1, 1);
+
+        assert!(board.game_won);
+        assert!(board.game_over);
+    }
+}
@@ -0,0 +1,460 @@
+//! The JSON board file format, available with the `serde` feature.
+//!
+//! One format covers bare layouts, games in progress and finished games:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "width": 9,
+//!   "height": 9,
+//!   "mine_count": 10,
+//!   "mines": [[0, 3], [5, 1], ...],
+//!   "revealed": [[4, 4], [4, 5]],
+//!   "flagged": [[0, 3]],
+//!   "questioned": [],
+//!   "status": "playing",
+//!   "seed": 1234,
+//!   "placement": "safe-opening",
+//!   "no_guess": false,
+//!   "question_marks": false,
+//!   "assisted": false,
+//!   "hints_used": 0,
+//!   "clicks": { "left": 2, "right": 1, "chord": 0, "effective": 3 }
+//! }
+//! ```
+//!
+//! * `version` must be [`BOARD_FILE_VERSION`]. Unknown fields are rejected.
+//! * Positions are `[x, y]` pairs with `0 <= x < width` and `0 <= y < height`.
+//! * `mines` lists exactly `mine_count` distinct positions, or is empty for a board
+//!   whose mines are placed on the first click (nothing may be revealed yet).
+//! * `revealed`, `flagged` and `questioned` give cell states; every other cell is
+//!   hidden, and a cell may appear in at most one list. Numbers are derived from the
+//!   mines and not stored.
+//! * `status` is `playing`, `won` (every safe cell revealed, no mine revealed) or
+//!   `lost` (at least one mine revealed).
+//! * Everything after `status` is optional and defaults to zero/false and the default
+//!   placement policy.
+//!
+//! Boards read back are not started; call [`GameBoard::start_game`] before playing.
+
+use super::board::{GameBoard, LayoutError};
+use super::cell::CellState;
+use super::placement::PlacementPolicy;
+use super::stats::Clicks;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const BOARD_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
+/// The on-disk shape of a board; see the module docs for the rules it must follow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoardFile {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub mine_count: usize,
+    #[serde(default)]
+    pub mines: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub revealed: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub flagged: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub questioned: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub placement: PlacementPolicy,
+    #[serde(default)]
+    pub no_guess: bool,
+    #[serde(default)]
+    pub question_marks: bool,
+    #[serde(default)]
+    pub assisted: bool,
+    #[serde(default)]
+    pub hints_used: usize,
+    #[serde(default)]
+    pub clicks: Clicks,
+}
+
+#[derive(Debug)]
+pub enum BoardFileError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+    EmptyBoard,
+    TooManyMines { mine_count: usize, cells: usize },
+    Layout(LayoutError),
+    CellOutOfBounds { x: usize, y: usize },
+    ConflictingState { x: usize, y: usize },
+    RevealedBeforeMines,
+    StatusMismatch(GameStatus),
+}
+
+impl fmt::Display for BoardFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardFileError::Json(e) => write!(f, "invalid JSON: {}", e),
+            BoardFileError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported board file version {} (expected {})",
+                version, BOARD_FILE_VERSION
+            ),
+            BoardFileError::EmptyBoard => write!(f, "width and height must be at least 1"),
+            BoardFileError::TooManyMines { mine_count, cells } => write!(
+                f,
+                "{} mines do not fit on a board of {} cells",
+                mine_count, cells
+            ),
+            BoardFileError::Layout(e) => write!(f, "{}", e),
+            BoardFileError::CellOutOfBounds { x, y } => {
+                write!(f, "cell ({}, {}) is off the board", x, y)
+            }
+            BoardFileError::ConflictingState { x, y } => {
+                write!(f, "cell ({}, {}) is given more than one state", x, y)
+            }
+            BoardFileError::RevealedBeforeMines => {
+                write!(f, "cells are revealed but no mines are placed")
+            }
+            BoardFileError::StatusMismatch(status) => {
+                write!(f, "cell states do not match status {:?}", status)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardFileError {}
+
+impl From<GameBoard> for BoardFile {
+    fn from(board: GameBoard) -> Self {
+        let cells_in = |state: CellState| {
+            let mut positions = Vec::new();
+            for (y, row) in board.board.iter().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    if cell.state == state {
+                        positions.push((x, y));
+                    }
+                }
+            }
+            positions
+        };
+
+        BoardFile {
+            version: BOARD_FILE_VERSION,
+            width: board.width,
+            height: board.height,
+            mine_count: board.mine_count,
+            mines: if board.first_click {
+                Vec::new()
+            } else {
+                board.mine_positions()
+            },
+            revealed: cells_in(CellState::Revealed),
+            flagged: cells_in(CellState::Flagged),
+            questioned: cells_in(CellState::Questioned),
+            status: match (board.game_over, board.game_won) {
+                (true, true) => GameStatus::Won,
+                (true, false) => GameStatus::Lost,
+                _ => GameStatus::Playing,
+            },
+            seed: board.seed,
+            placement: board.placement,
+            no_guess: board.no_guess,
+            question_marks: board.question_marks,
+            assisted: board.assisted,
+            hints_used: board.hints_used,
+            clicks: board.clicks,
+        }
+    }
+}
+
+impl TryFrom<BoardFile> for GameBoard {
+    type Error = BoardFileError;
+
+    fn try_from(file: BoardFile) -> Result<Self, Self::Error> {
+        if file.version != BOARD_FILE_VERSION {
+            return Err(BoardFileError::UnsupportedVersion(file.version));
+        }
+        if file.width == 0 || file.height == 0 {
+            return Err(BoardFileError::EmptyBoard);
+        }
+        let cells = file.width * file.height;
+        if file.mine_count > cells {
+            return Err(BoardFileError::TooManyMines {
+                mine_count: file.mine_count,
+                cells,
+            });
+        }
+
+        let layout = if file.mines.is_empty() {
+            if !file.revealed.is_empty() || file.status != GameStatus::Playing {
+                return Err(BoardFileError::RevealedBeforeMines);
+            }
+            GameBoard::with_seed(file.width, file.height, file.mine_count, file.seed)
+        } else {
+            if file.mines.len() != file.mine_count {
+                return Err(BoardFileError::Layout(LayoutError::MineCountMismatch {
+                    expected: file.mine_count,
+                    found: file.mines.len(),
+                }));
+            }
+            GameBoard::from_layout(file.width, file.height, &file.mines)
+                .map_err(BoardFileError::Layout)?
+        };
+
+        let mut cells = layout.board;
+        let mut assigned = vec![vec![false; file.width]; file.height];
+        for (positions, state) in [
+            (&file.revealed, CellState::Revealed),
+            (&file.flagged, CellState::Flagged),
+            (&file.questioned, CellState::Questioned),
+        ] {
+            for &(x, y) in positions {
+                if x >= file.width || y >= file.height {
+                    return Err(BoardFileError::CellOutOfBounds { x, y });
+                }
+                if assigned[y][x] {
+                    return Err(BoardFileError::ConflictingState { x, y });
+                }
+                assigned[y][x] = true;
+                cells[y][x].state = state;
+            }
+        }
+
+        let mut board =
+            GameBoard::from_cells(cells, file.mine_count).map_err(BoardFileError::Layout)?;
+
+        let consistent = match file.status {
+            GameStatus::Playing => !board.game_over,
+            GameStatus::Won => board.game_won,
+            GameStatus::Lost => board.game_over && !board.game_won,
+        };
+        if !consistent {
+            return Err(BoardFileError::StatusMismatch(file.status));
+        }
+
+        board.game_over = file.status != GameStatus::Playing;
+        board.game_won = file.status == GameStatus::Won;
+        board.seed = file.seed;
+        board.placement = file.placement;
+        board.no_guess = file.no_guess;
+        board.question_marks = file.question_marks;
+        board.assisted = file.assisted;
+        board.hints_used = file.hints_used;
+        board.clicks = file.clicks;
+        Ok(board)
+    }
+}
+
+impl GameBoard {
+    /// The board as pretty-printed JSON in the board file format.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&BoardFile::from(self.clone()))
+            .expect("board files always serialize")
+    }
+
+    /// Reads and validates a board in the board file format.
+    pub fn from_json(json: &str) -> Result<Self, BoardFileError> {
+        let file: BoardFile = serde_json::from_str(json).map_err(BoardFileError::Json)?;
+        GameBoard::try_from(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Cell, CellContent};
+    use crate::Difficulty;
+
+    fn layout_json(extra: &str) -> String {
+        format!(
+            r#"{{"version": 1, "width": 3, "height": 3, "mine_count": 1, "mines": [[0, 0]]{}}}"#,
+            extra
+        )
+    }
+
+    #[test]
+    fn test_layout_round_trip() {
+        let board = GameBoard::from_json(&layout_json("")).unwrap();
+
+        assert_eq!(board.mine_positions(), vec![(0, 0)]);
+        assert_eq!(board.get_cell_content(1, 1), CellContent::Number(1));
+        assert!(!board.first_click);
+        assert!(!board.game_started);
+        assert_eq!(
+            GameBoard::from_json(&board.to_json()).unwrap().board,
+            board.board
+        );
+    }
+
+    #[test]
+    fn test_in_progress_round_trip() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 31);
+        board.question_marks = true;
+        board.start_game();
+        board.reveal_cell(4, 4);
+        let (mx, my) = board.mine_positions()[0];
+        board.toggle_flag(mx, my);
+
+        let restored = GameBoard::from_json(&board.to_json()).unwrap();
+
+        assert_eq!(restored.board, board.board);
+        assert_eq!(restored.seed, 31);
+        assert!(restored.question_marks);
+        assert_eq!(restored.flags_placed(), 1);
+        assert!(!restored.game_over);
+    }
+
+    #[test]
+    fn test_finished_games_round_trip() {
+        let mut lost = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        lost.start_game();
+        lost.reveal_cell(0, 0);
+        let restored = GameBoard::from_json(&lost.to_json()).unwrap();
+        assert!(restored.game_over && !restored.game_won);
+
+        let mut won = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        won.start_game();
+        won.reveal_cell(2, 2);
+        assert!(won.game_won);
+        let restored = GameBoard::from_json(&won.to_json()).unwrap();
+        assert!(restored.game_over && restored.game_won);
+    }
+
+    #[test]
+    fn test_unplaced_layout_keeps_first_click() {
+        let board = GameBoard::with_seed(8, 8, 10, 4);
+        let restored = GameBoard::from_json(&board.to_json()).unwrap();
+
+        assert!(restored.first_click);
+        assert_eq!(restored.mine_count, 10);
+        assert_eq!(restored.seed, 4);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_mine_count() {
+        let json = layout_json("").replace(r#""mine_count": 1"#, r#""mine_count": 2"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::Layout(LayoutError::MineCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_mismatched_dimensions() {
+        let json = layout_json("").replace("[[0, 0]]", "[[3, 0]]");
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::Layout(LayoutError::OutOfBounds { x: 3, y: 0 })
+        ));
+
+        let json = layout_json(r#", "revealed": [[1, 5]]"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::CellOutOfBounds { x: 1, y: 5 }
+        ));
+
+        let json = layout_json("").replace(r#""mine_count": 1"#, r#""mine_count": 10"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::TooManyMines { .. }
+        ));
+
+        let json = layout_json("").replace(r#""width": 3"#, r#""width": 0"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::EmptyBoard
+        ));
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_states() {
+        let json = layout_json(r#", "revealed": [[1, 1]], "flagged": [[1, 1]]"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::ConflictingState { x: 1, y: 1 }
+        ));
+
+        let json = layout_json(r#", "status": "won""#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::StatusMismatch(GameStatus::Won)
+        ));
+
+        let json = layout_json(r#", "revealed": [[0, 0]]"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::StatusMismatch(GameStatus::Playing)
+        ));
+
+        let json =
+            r#"{"version": 1, "width": 3, "height": 3, "mine_count": 1, "revealed": [[1, 1]]}"#;
+        assert!(matches!(
+            GameBoard::from_json(json).unwrap_err(),
+            BoardFileError::RevealedBeforeMines
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unknown_version_and_fields() {
+        let json = layout_json("").replace(r#""version": 1"#, r#""version": 2"#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::UnsupportedVersion(2)
+        ));
+
+        let json = layout_json(r#", "colour": "red""#);
+        assert!(matches!(
+            GameBoard::from_json(&json).unwrap_err(),
+            BoardFileError::Json(_)
+        ));
+    }
+
+    #[test]
+    fn test_serde_derives_go_through_board_file() {
+        let board = GameBoard::from_layout(3, 3, &[(2, 2)]).unwrap();
+        let value = serde_json::to_value(&board).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(value["mines"], serde_json::json!([[2, 2]]));
+
+        let bad = serde_json::json!({"version": 1, "width": 3, "height": 3, "mine_count": 2, "mines": [[2, 2]]});
+        assert!(serde_json::from_value::<GameBoard>(bad).is_err());
+    }
+
+    #[test]
+    fn test_cell_and_difficulty_derives() {
+        let cell = Cell {
+            state: CellState::Flagged,
+            content: CellContent::Number(3),
+        };
+        let json = serde_json::to_string(&cell).unwrap();
+        assert_eq!(json, r#"{"state":"flagged","content":{"number":3}}"#);
+        assert_eq!(serde_json::from_str::<Cell>(&json).unwrap(), cell);
+
+        let custom = Difficulty::Custom {
+            width: 20,
+            height: 10,
+            mines: 30,
+        };
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(serde_json::from_str::<Difficulty>(&json).unwrap(), custom);
+        assert_eq!(
+            serde_json::to_string(&Difficulty::Expert).unwrap(),
+            r#""expert""#
+        );
+    }
+}
@@ -0,0 +1,41 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CellState {
+    Hidden,
+    Revealed,
+    Flagged,
+    Questioned,
+}
+
+impl CellState {
+    /// Hidden and question-marked cells can both be revealed; flags protect a cell.
+    pub fn is_revealable(&self) -> bool {
+        matches!(self, CellState::Hidden | CellState::Questioned)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CellContent {
+    Empty,
+    Number(u8),
+    Mine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cell {
+    pub state: CellState,
+    pub content: CellContent,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            state: CellState::Hidden,
+            content: CellContent::Empty,
+        }
+    }
+}
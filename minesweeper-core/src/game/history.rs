@@ -0,0 +1,136 @@
+use super::cell::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveKind {
+    Reveal,
+    Flag,
+    Chord,
+}
+
+/// One cell touched by a move, with its value before and after.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellChange {
+    pub x: usize,
+    pub y: usize,
+    pub before: Cell,
+    pub after: Cell,
+}
+
+/// The board-wide values a move can change besides the cells themselves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardStatus {
+    pub game_over: bool,
+    pub game_won: bool,
+    pub first_click: bool,
+    pub flags_placed: usize,
+    pub safe_cells_remaining: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Move {
+    pub kind: MoveKind,
+    pub x: usize,
+    pub y: usize,
+    pub changes: Vec<CellChange>,
+    pub before: BoardStatus,
+    pub after: BoardStatus,
+}
+
+/// Moves that can be undone, and undone moves that can be redone.
+#[derive(Debug, Clone, Default)]
+pub struct MoveHistory {
+    done: Vec<Move>,
+    undone: Vec<Move>,
+}
+
+impl MoveHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a new move. Anything that was undone can no longer be redone.
+    pub fn push(&mut self, mv: Move) {
+        self.done.push(mv);
+        self.undone.clear();
+    }
+
+    pub fn take_undo(&mut self) -> Option<Move> {
+        let mv = self.done.pop()?;
+        self.undone.push(mv.clone());
+        Some(mv)
+    }
+
+    pub fn take_redo(&mut self) -> Option<Move> {
+        let mv = self.undone.pop()?;
+        self.done.push(mv.clone());
+        Some(mv)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.done
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_move(x: usize) -> Move {
+        let status = BoardStatus {
+            game_over: false,
+            game_won: false,
+            first_click: false,
+            flags_placed: 0,
+            safe_cells_remaining: 0,
+        };
+        Move {
+            kind: MoveKind::Flag,
+            x,
+            y: 0,
+            changes: Vec::new(),
+            before: status,
+            after: status,
+        }
+    }
+
+    #[test]
+    fn test_undo_and_redo_order() {
+        let mut history = MoveHistory::new();
+        history.push(test_move(1));
+        history.push(test_move(2));
+
+        assert_eq!(history.take_undo().map(|m| m.x), Some(2));
+        assert_eq!(history.take_undo().map(|m| m.x), Some(1));
+        assert!(history.take_undo().is_none());
+
+        assert_eq!(history.take_redo().map(|m| m.x), Some(1));
+        assert_eq!(history.moves().len(), 1);
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn test_new_move_clears_redo() {
+        let mut history = MoveHistory::new();
+        history.push(test_move(1));
+        history.take_undo();
+        assert!(history.can_redo());
+
+        history.push(test_move(2));
+
+        assert!(!history.can_redo());
+        assert!(history.can_undo());
+    }
+}
@@ -0,0 +1,20 @@
+pub mod ascii;
+pub mod board;
+#[cfg(feature = "serde")]
+pub mod board_file;
+pub mod cell;
+pub mod history;
+pub mod no_guess;
+pub mod placement;
+pub mod post_mortem;
+pub mod stats;
+pub mod three_bv;
+
+pub use ascii::AsciiBoardError;
+pub use board::{GameBoard, LayoutError};
+#[cfg(feature = "serde")]
+pub use board_file::{BoardFile, BoardFileError, GameStatus, BOARD_FILE_VERSION};
+pub use cell::{Cell, CellContent, CellState};
+pub use placement::PlacementPolicy;
+pub use post_mortem::{LosingClick, PostMortem};
+pub use stats::{Clicks, GameStats};
@@ -0,0 +1,255 @@
+use super::board::GameBoard;
+use super::cell::CellContent;
+use crate::solver::{deduce, BoardView, VisibleCell};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Knowledge {
+    Unknown,
+    Revealed,
+    Mine,
+}
+
+/// Work a no-guess search may do before settling for a layout that may need guessing,
+/// counted in cells visited: one pass over the board per layout tried and per solver
+/// step. Around a second in a release build, whatever the board size.
+pub(crate) const NO_GUESS_WORK_BUDGET: u64 = 5_000_000;
+
+/// Mine density above which a no-guess layout is seldom found before generation gives
+/// up; at 25% most Expert-sized boards fall back to one that needs guessing.
+pub const NO_GUESS_MAX_DENSITY: f64 = 0.25;
+
+/// Density limit for boards larger than Expert, where each layout takes longer to
+/// check and fewer fit in the work budget.
+pub const NO_GUESS_MAX_LARGE_DENSITY: f64 = 0.20;
+
+/// Largest board, in cells, whose solve reliably fits in the work budget.
+pub const NO_GUESS_MAX_CELLS: usize = 2500;
+
+/// Whether no-guess generation can be expected to succeed for a board of this size.
+pub fn is_no_guess_practical(width: usize, height: usize, mine_count: usize) -> bool {
+    let cells = width * height;
+    let max_density = if cells <= 30 * 16 {
+        NO_GUESS_MAX_DENSITY
+    } else {
+        NO_GUESS_MAX_LARGE_DENSITY
+    };
+    cells <= NO_GUESS_MAX_CELLS && mine_count as f64 <= max_density * cells as f64
+}
+
+/// Plays the board from `(start_x, start_y)` using only deductions a player could make,
+/// and reports whether every safe cell can be revealed without guessing.
+pub fn is_solvable_without_guessing(board: &GameBoard, start_x: usize, start_y: usize) -> bool {
+    is_solvable_within(board, start_x, start_y, &mut { u64::MAX }) == Some(true)
+}
+
+/// Like [`is_solvable_without_guessing`], but each solver pass costs the board's cell
+/// count from `budget`. Returns `None` once the budget runs out, so the search takes
+/// the same amount of work for a given board on any machine.
+pub(crate) fn is_solvable_within(
+    board: &GameBoard,
+    start_x: usize,
+    start_y: usize,
+    budget: &mut u64,
+) -> Option<bool> {
+    let mut simulation = Simulation::new(board);
+
+    if simulation.is_mine(simulation.index(start_x, start_y)) {
+        return Some(false);
+    }
+
+    simulation.reveal(simulation.index(start_x, start_y));
+
+    let pass_cost = (board.width * board.height) as u64;
+    while simulation.safe_remaining > 0 {
+        *budget = budget.checked_sub(pass_cost)?;
+        if !simulation.step() {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+struct Simulation<'a> {
+    board: &'a GameBoard,
+    knowledge: Vec<Knowledge>,
+    safe_remaining: usize,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(board: &'a GameBoard) -> Self {
+        let total = board.width * board.height;
+        Self {
+            board,
+            knowledge: vec![Knowledge::Unknown; total],
+            safe_remaining: total - board.mine_count,
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.board.width + x
+    }
+
+    fn position(&self, index: usize) -> (usize, usize) {
+        (index % self.board.width, index / self.board.width)
+    }
+
+    fn content(&self, index: usize) -> CellContent {
+        let (x, y) = self.position(index);
+        self.board.board[y][x].content
+    }
+
+    fn is_mine(&self, index: usize) -> bool {
+        matches!(self.content(index), CellContent::Mine)
+    }
+
+    fn neighbors(&self, index: usize) -> Vec<usize> {
+        let (x, y) = self.position(index);
+        let mut neighbors = Vec::with_capacity(8);
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+
+                if nx >= 0
+                    && nx < self.board.width as i32
+                    && ny >= 0
+                    && ny < self.board.height as i32
+                {
+                    neighbors.push(self.index(nx as usize, ny as usize));
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn reveal(&mut self, index: usize) {
+        let mut queue = VecDeque::from([index]);
+
+        while let Some(current) = queue.pop_front() {
+            if self.knowledge[current] != Knowledge::Unknown {
+                continue;
+            }
+
+            self.knowledge[current] = Knowledge::Revealed;
+            self.safe_remaining -= 1;
+
+            if matches!(self.content(current), CellContent::Empty) {
+                queue.extend(
+                    self.neighbors(current)
+                        .into_iter()
+                        .filter(|&n| self.knowledge[n] == Knowledge::Unknown),
+                );
+            }
+        }
+    }
+
+    fn mark_mine(&mut self, index: usize) {
+        if self.knowledge[index] == Knowledge::Unknown {
+            self.knowledge[index] = Knowledge::Mine;
+        }
+    }
+
+    /// What a player would see at this point of the simulation, with the deduced
+    /// mines flagged.
+    fn view(&self) -> BoardView {
+        let mut view = BoardView::new(self.board.width, self.board.height, self.board.mine_count);
+        for (index, knowledge) in self.knowledge.iter().enumerate() {
+            let (x, y) = self.position(index);
+            let visible = match knowledge {
+                Knowledge::Unknown => continue,
+                Knowledge::Mine => VisibleCell::Flagged,
+                Knowledge::Revealed => match self.content(index) {
+                    CellContent::Number(n) => VisibleCell::Revealed(n),
+                    _ => VisibleCell::Revealed(0),
+                },
+            };
+            view.set(x, y, visible);
+        }
+        view
+    }
+
+    /// Applies everything the solver can deduce. Returns false when no progress can be
+    /// made.
+    fn step(&mut self) -> bool {
+        let deductions = deduce(&self.view());
+        if deductions.is_empty() {
+            return false;
+        }
+
+        for (x, y) in deductions.mines {
+            self.mark_mine(self.index(x, y));
+        }
+        for (x, y) in deductions.safe {
+            self.reveal(self.index(x, y));
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_with_mines(width: usize, height: usize, mines: &[(usize, usize)]) -> GameBoard {
+        GameBoard::from_layout(width, height, mines).unwrap()
+    }
+
+    #[test]
+    fn test_solvable_single_opening() {
+        let board = board_with_mines(4, 4, &[(3, 3)]);
+
+        assert!(is_solvable_without_guessing(&board, 0, 0));
+    }
+
+    #[test]
+    fn test_start_on_mine_is_not_solvable() {
+        let board = board_with_mines(4, 4, &[(0, 0)]);
+
+        assert!(!is_solvable_without_guessing(&board, 0, 0));
+    }
+
+    #[test]
+    fn test_fifty_fifty_is_not_solvable() {
+        // From the revealed side, (2, 0) and (2, 1) look exactly alike.
+        let board = board_with_mines(3, 2, &[(2, 0)]);
+
+        assert!(!is_solvable_without_guessing(&board, 0, 0));
+    }
+
+    #[test]
+    fn test_global_mine_count_resolves_last_cells() {
+        // The wall of mines hides the right side; only the total mine count clears it.
+        let board = board_with_mines(5, 3, &[(2, 0), (2, 1), (2, 2)]);
+
+        assert!(is_solvable_without_guessing(&board, 0, 0));
+    }
+
+    #[test]
+    fn test_no_guess_practical_density() {
+        assert!(is_no_guess_practical(30, 16, 99));
+        assert!(is_no_guess_practical(50, 50, 500));
+        assert!(!is_no_guess_practical(16, 16, 90));
+        assert!(!is_no_guess_practical(50, 50, 600));
+        assert!(!is_no_guess_practical(100, 100, 1500));
+    }
+
+    #[test]
+    fn test_search_stops_when_the_budget_runs_out() {
+        let board = board_with_mines(5, 3, &[(2, 0), (2, 1), (2, 2)]);
+
+        let mut budget = 14;
+        assert_eq!(is_solvable_within(&board, 0, 0, &mut budget), None);
+        let mut budget = 1000;
+        assert_eq!(is_solvable_within(&board, 0, 0, &mut budget), Some(true));
+        assert!(budget < 1000);
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Which cells around the first click are kept free of mines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PlacementPolicy {
+    /// Only the clicked cell is safe.
+    SafeCell,
+    /// The clicked cell and its 8 neighbours are safe, so the first click always opens a
+    /// cascade. Falls back to `SafeCell` when the board is too dense.
+    #[default]
+    SafeOpening,
+    /// Mines may be placed anywhere, including under the first click.
+    None,
+}
+
+impl fmt::Display for PlacementPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PlacementPolicy::SafeCell => "safe-cell",
+            PlacementPolicy::SafeOpening => "safe-opening",
+            PlacementPolicy::None => "none",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for PlacementPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "safe-cell" => Ok(PlacementPolicy::SafeCell),
+            "safe-opening" => Ok(PlacementPolicy::SafeOpening),
+            "none" => Ok(PlacementPolicy::None),
+            _ => Err(format!(
+                "unknown placement policy '{}', expected safe-cell, safe-opening or none",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy() {
+        assert_eq!(PlacementPolicy::default(), PlacementPolicy::SafeOpening);
+    }
+
+    #[test]
+    fn test_policy_round_trip() {
+        for policy in [
+            PlacementPolicy::SafeCell,
+            PlacementPolicy::SafeOpening,
+            PlacementPolicy::None,
+        ] {
+            assert_eq!(policy.to_string().parse::<PlacementPolicy>(), Ok(policy));
+        }
+    }
+
+    #[test]
+    fn test_policy_parse_error() {
+        assert!("everywhere".parse::<PlacementPolicy>().is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use super::board::GameBoard;
+use super::cell::{CellContent, CellState};
+use super::history::MoveKind;
+use crate::solver::{self, BoardView, Hint};
+
+/// What went wrong in a lost game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostMortem {
+    /// The move that hit a mine. `None` when it isn't in the undo history, as for a
+    /// board loaded already lost.
+    pub losing_click: Option<LosingClick>,
+    /// Flags placed on cells without a mine.
+    pub wrong_flags: Vec<(usize, usize)>,
+}
+
+/// The mine that ended the game, judged by what the numbers showed just before it was
+/// hit. Flags are ignored, since a wrong one may be what caused the loss.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosingClick {
+    pub x: usize,
+    pub y: usize,
+    /// Chance this cell held a mine, given the numbers.
+    pub probability: f64,
+    /// A safe move the numbers proved, or the safest guess if there was none.
+    pub best_move: Option<Hint>,
+}
+
+impl LosingClick {
+    /// True when no cell was provably safe, so some guess was unavoidable.
+    pub fn was_forced(&self) -> bool {
+        !matches!(self.best_move, Some(Hint::Safe { .. }))
+    }
+}
+
+impl GameBoard {
+    /// Analysis of a lost game; `None` unless the game is over and lost.
+    pub fn post_mortem(&self) -> Option<PostMortem> {
+        if !self.game_over || self.game_won {
+            return None;
+        }
+
+        let mut wrong_flags = Vec::new();
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell.state == CellState::Flagged && cell.content != CellContent::Mine {
+                    wrong_flags.push((x, y));
+                }
+            }
+        }
+
+        Some(PostMortem {
+            losing_click: self.losing_click(),
+            wrong_flags,
+        })
+    }
+
+    fn losing_click(&self) -> Option<LosingClick> {
+        let mv = self.history().moves().last()?;
+        if !mv.after.game_over || mv.after.game_won || mv.before.game_over {
+            return None;
+        }
+
+        // A reveal hits the clicked cell; a chord hits the first mine it reveals
+        let (x, y) = match mv.kind {
+            MoveKind::Reveal => (mv.x, mv.y),
+            _ => mv
+                .changes
+                .iter()
+                .find(|change| {
+                    change.after.state == CellState::Revealed
+                        && change.after.content == CellContent::Mine
+                })
+                .map(|change| (change.x, change.y))?,
+        };
+
+        let mut before = self.clone();
+        for change in &mv.changes {
+            before.board[change.y][change.x] = change.before;
+        }
+        let view = BoardView::from_board(&before).without_flags();
+
+        let probability = solver::mine_probabilities(&view)?.get(x, y)?;
+        Some(LosingClick {
+            x,
+            y,
+            probability,
+            best_move: solver::hint(&view),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_move_was_available() {
+        let mut board = GameBoard::from_ascii(
+            "*1
+             11
+             f.",
+        )
+        .unwrap();
+        board.start_game();
+        board.reveal_cell(0, 0);
+        let post_mortem = board.post_mortem().unwrap();
+
+        assert_eq!(post_mortem.wrong_flags, vec![(0, 2)]);
+        let click = post_mortem.losing_click.unwrap();
+        assert_eq!((click.x, click.y), (0, 0));
+        assert_eq!(click.probability, 1.0);
+        assert!(!click.was_forced());
+    }
+
+    #[test]
+    fn test_forced_guess() {
+        // A 50/50 between (0, 0) and (1, 0) with nothing else to open
+        let mut board = GameBoard::from_ascii(
+            "*.
+             11",
+        )
+        .unwrap();
+        board.start_game();
+        board.reveal_cell(0, 0);
+        let click = board.post_mortem().unwrap().losing_click.unwrap();
+
+        assert_eq!(click.probability, 0.5);
+        assert!(click.was_forced());
+        assert!(matches!(click.best_move, Some(Hint::Guess { .. })));
+    }
+
+    #[test]
+    fn test_chord_on_wrong_flag() {
+        let mut board = GameBoard::from_ascii(
+            "*.
+             1.
+             ..",
+        )
+        .unwrap();
+        board.start_game();
+        board.toggle_flag(1, 0);
+        board.chord(0, 1);
+        let post_mortem = board.post_mortem().unwrap();
+
+        assert_eq!(post_mortem.wrong_flags, vec![(1, 0)]);
+        let click = post_mortem.losing_click.unwrap();
+        assert_eq!((click.x, click.y), (0, 0));
+    }
+
+    #[test]
+    fn test_no_post_mortem_without_a_loss() {
+        let mut board = GameBoard::from_layout(2, 1, &[(0, 0)]).unwrap();
+        board.start_game();
+        assert!(board.post_mortem().is_none());
+
+        let lost = GameBoard::from_ascii("!1").unwrap();
+        let post_mortem = lost.post_mortem().unwrap();
+        assert!(post_mortem.losing_click.is_none());
+    }
+}
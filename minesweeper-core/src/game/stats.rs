@@ -0,0 +1,96 @@
+use super::board::GameBoard;
+use std::time::Duration;
+
+/// Clicks made during a game, counted as the player makes them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clicks {
+    /// Reveals.
+    pub left: usize,
+    /// Flag toggles.
+    pub right: usize,
+    pub chord: usize,
+    /// Clicks of any kind that changed the board.
+    pub effective: usize,
+}
+
+impl Clicks {
+    pub fn total(&self) -> usize {
+        self.left + self.right + self.chord
+    }
+}
+
+/// Speed and efficiency of a game, as speedrunners rank them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameStats {
+    pub three_bv: usize,
+    /// The part of the 3BV the revealed cells account for; all of it in a won game.
+    pub three_bv_solved: usize,
+    pub clicks: Clicks,
+    pub elapsed: Duration,
+}
+
+impl GameStats {
+    /// Stats for `board` after `elapsed` on the clock; `None` until the mines are placed.
+    pub fn new(board: &GameBoard, elapsed: Duration) -> Option<Self> {
+        Some(Self {
+            three_bv: board.three_bv()?,
+            three_bv_solved: board.three_bv_solved()?,
+            clicks: board.clicks,
+            elapsed,
+        })
+    }
+
+    /// Solved 3BV per second.
+    pub fn three_bv_per_second(&self) -> Option<f64> {
+        let seconds = self.elapsed.as_secs_f64();
+        (seconds > 0.0).then(|| self.three_bv_solved as f64 / seconds)
+    }
+
+    /// Solved 3BV per click, 1.0 for a perfect game.
+    pub fn ioe(&self) -> Option<f64> {
+        let total = self.clicks.total();
+        (total > 0).then(|| self.three_bv_solved as f64 / total as f64)
+    }
+
+    /// Share of clicks that changed the board.
+    pub fn correctness(&self) -> Option<f64> {
+        let total = self.clicks.total();
+        (total > 0).then(|| self.clicks.effective as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates() {
+        let stats = GameStats {
+            three_bv: 30,
+            three_bv_solved: 20,
+            clicks: Clicks {
+                left: 15,
+                right: 5,
+                chord: 5,
+                effective: 20,
+            },
+            elapsed: Duration::from_secs(10),
+        };
+
+        assert_eq!(stats.three_bv_per_second(), Some(2.0));
+        assert_eq!(stats.ioe(), Some(0.8));
+        assert_eq!(stats.correctness(), Some(0.8));
+    }
+
+    #[test]
+    fn test_no_rates_without_time_or_clicks() {
+        let board = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        let stats = GameStats::new(&board, Duration::ZERO).unwrap();
+
+        assert_eq!(stats.three_bv_solved, 0);
+        assert_eq!(stats.three_bv_per_second(), None);
+        assert_eq!(stats.ioe(), None);
+        assert!(GameStats::new(&GameBoard::new(9, 9, 10), Duration::ZERO).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use super::board::GameBoard;
+use super::cell::{CellContent, CellState};
+
+impl GameBoard {
+    /// The board's 3BV: the fewest left clicks that clear it without flags. Each opening
+    /// counts once, plus every number that no opening uncovers. Worked out whenever the
+    /// layout is set; `None` until the mines are placed.
+    pub fn three_bv(&self) -> Option<usize> {
+        (!self.first_click).then_some(self.layout_three_bv)
+    }
+
+    /// How much of the 3BV has been done: openings with their cells revealed, and
+    /// revealed numbers outside any opening. `None` until the mines are placed.
+    pub fn three_bv_solved(&self) -> Option<usize> {
+        (!self.first_click).then(|| self.count_three_bv(true))
+    }
+
+    /// Counts the layout's 3BV, or only the solved part when `solved_only` is set.
+    pub(super) fn count_three_bv(&self, solved_only: bool) -> usize {
+        let revealed = |x: usize, y: usize| self.board[y][x].state == CellState::Revealed;
+        let mut covered = vec![vec![false; self.width]; self.height];
+        let mut clicks = 0;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if covered[y][x] || self.board[y][x].content != CellContent::Empty {
+                    continue;
+                }
+                // Revealing any empty cell opens the whole region
+                let mut opened = revealed(x, y);
+                covered[y][x] = true;
+                let mut worklist = vec![(x, y)];
+                while let Some((cx, cy)) = worklist.pop() {
+                    for (nx, ny) in self.neighbors(cx, cy) {
+                        if covered[ny][nx] {
+                            continue;
+                        }
+                        covered[ny][nx] = true;
+                        if self.board[ny][nx].content == CellContent::Empty {
+                            opened |= revealed(nx, ny);
+                            worklist.push((nx, ny));
+                        }
+                    }
+                }
+                if opened || !solved_only {
+                    clicks += 1;
+                }
+            }
+        }
+
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if matches!(cell.content, CellContent::Number(_))
+                    && !covered[y][x]
+                    && (revealed(x, y) || !solved_only)
+                {
+                    clicks += 1;
+                }
+            }
+        }
+        clicks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openings_and_isolated_numbers() {
+        // One opening on the right uncovers the column of numbers next to it; the five
+        // numbers left of and below the mines each take a click
+        let board = GameBoard::from_ascii(
+            ".*...
+             .*...
+             .*...
+             .....",
+        )
+        .unwrap();
+        assert_eq!(board.three_bv(), Some(6));
+        assert_eq!(board.three_bv_solved(), Some(0));
+    }
+
+    #[test]
+    fn test_solved_counts_openings_once() {
+        let mut board = GameBoard::from_layout(5, 4, &[(1, 0), (1, 1), (1, 2)]).unwrap();
+        board.start_game();
+        board.reveal_cell(4, 0);
+        assert_eq!(board.three_bv_solved(), Some(1));
+        board.reveal_cell(0, 3);
+        assert_eq!(board.three_bv_solved(), Some(2));
+    }
+
+    #[test]
+    fn test_no_three_bv_before_mines_are_placed() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 3);
+        assert_eq!(board.three_bv(), None);
+        board.start_game();
+        board.reveal_cell(4, 4);
+        assert_eq!(board.three_bv(), Some(board.count_three_bv(false)));
+
+        let all_numbers = GameBoard::from_ascii("*.*\n.*.").unwrap();
+        assert_eq!(all_numbers.three_bv(), Some(3));
+    }
+}
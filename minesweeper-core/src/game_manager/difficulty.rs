@@ -0,0 +1,241 @@
+use std::fmt;
+
+pub const MIN_CUSTOM_DIMENSION: usize = 5;
+pub const MAX_CUSTOM_DIMENSION: usize = 1000;
+/// Highest share of the board that may be mines in a custom game.
+pub const MAX_CUSTOM_DENSITY: f64 = 0.85;
+/// Cells kept free for the first-click opening (the clicked cell and its neighbours).
+pub const FIRST_CLICK_RESERVED_CELLS: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+    Custom {
+        width: usize,
+        height: usize,
+        mines: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DifficultyError {
+    TooSmall,
+    TooLarge,
+    NoMines,
+    TooDense { max_mines: usize },
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifficultyError::TooSmall => write!(
+                f,
+                "width and height must be at least {}",
+                MIN_CUSTOM_DIMENSION
+            ),
+            DifficultyError::TooLarge => write!(
+                f,
+                "width and height must be at most {}",
+                MAX_CUSTOM_DIMENSION
+            ),
+            DifficultyError::NoMines => write!(f, "there must be at least one mine"),
+            DifficultyError::TooDense { max_mines } => {
+                write!(f, "at most {} mines fit on this board", max_mines)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DifficultyError {}
+
+impl Difficulty {
+    /// Builds a validated custom difficulty.
+    pub fn custom(width: usize, height: usize, mines: usize) -> Result<Self, DifficultyError> {
+        let difficulty = Difficulty::Custom {
+            width,
+            height,
+            mines,
+        };
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
+
+    pub fn validate(&self) -> Result<(), DifficultyError> {
+        let (width, height, mines) = self.get_dimensions();
+
+        if width < MIN_CUSTOM_DIMENSION || height < MIN_CUSTOM_DIMENSION {
+            return Err(DifficultyError::TooSmall);
+        }
+        if width > MAX_CUSTOM_DIMENSION || height > MAX_CUSTOM_DIMENSION {
+            return Err(DifficultyError::TooLarge);
+        }
+        if mines == 0 {
+            return Err(DifficultyError::NoMines);
+        }
+
+        let cells = width * height;
+        let max_mines =
+            ((cells as f64 * MAX_CUSTOM_DENSITY) as usize).min(cells - FIRST_CLICK_RESERVED_CELLS);
+        if mines > max_mines {
+            return Err(DifficultyError::TooDense { max_mines });
+        }
+
+        Ok(())
+    }
+
+    pub fn get_dimensions(&self) -> (usize, usize, usize) {
+        match self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (30, 16, 99),
+            Difficulty::Custom {
+                width,
+                height,
+                mines,
+            } => (*width, *height, *mines),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Difficulty::Beginner => "Beginner".to_string(),
+            Difficulty::Intermediate => "Intermediate".to_string(),
+            Difficulty::Expert => "Expert".to_string(),
+            Difficulty::Custom {
+                width,
+                height,
+                mines,
+            } => format!("Custom {}×{}, {} mines", width, height, mines),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beginner_difficulty() {
+        let difficulty = Difficulty::Beginner;
+        let (width, height, mines) = difficulty.get_dimensions();
+
+        assert_eq!(width, 9);
+        assert_eq!(height, 9);
+        assert_eq!(mines, 10);
+    }
+
+    #[test]
+    fn test_intermediate_difficulty() {
+        let difficulty = Difficulty::Intermediate;
+        let (width, height, mines) = difficulty.get_dimensions();
+
+        assert_eq!(width, 16);
+        assert_eq!(height, 16);
+        assert_eq!(mines, 40);
+    }
+
+    #[test]
+    fn test_expert_difficulty() {
+        let difficulty = Difficulty::Expert;
+        let (width, height, mines) = difficulty.get_dimensions();
+
+        assert_eq!(width, 30);
+        assert_eq!(height, 16);
+        assert_eq!(mines, 99);
+    }
+
+    #[test]
+    fn test_difficulty_equality() {
+        assert_eq!(Difficulty::Beginner, Difficulty::Beginner);
+        assert_ne!(Difficulty::Beginner, Difficulty::Intermediate);
+        assert_ne!(Difficulty::Intermediate, Difficulty::Expert);
+        assert_ne!(Difficulty::Beginner, Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_difficulty_clone() {
+        let original = Difficulty::Expert;
+        let cloned = original;
+        assert_eq!(original, cloned);
+    }
+
+    #[test]
+    fn test_difficulty_debug() {
+        let difficulty = Difficulty::Beginner;
+        let debug_str = format!("{:?}", difficulty);
+        assert_eq!(debug_str, "Beginner");
+    }
+
+    #[test]
+    fn test_custom_difficulty_dimensions() {
+        let difficulty = Difficulty::custom(20, 12, 50).unwrap();
+
+        assert_eq!(difficulty.get_dimensions(), (20, 12, 50));
+        assert_eq!(difficulty.name(), "Custom 20×12, 50 mines");
+    }
+
+    #[test]
+    fn test_custom_difficulty_too_small() {
+        assert_eq!(Difficulty::custom(4, 10, 5), Err(DifficultyError::TooSmall));
+        assert_eq!(Difficulty::custom(10, 4, 5), Err(DifficultyError::TooSmall));
+    }
+
+    #[test]
+    fn test_custom_difficulty_too_large() {
+        assert_eq!(
+            Difficulty::custom(MAX_CUSTOM_DIMENSION + 1, 10, 5),
+            Err(DifficultyError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn test_custom_difficulty_needs_mines() {
+        assert_eq!(Difficulty::custom(10, 10, 0), Err(DifficultyError::NoMines));
+    }
+
+    #[test]
+    fn test_custom_difficulty_density_limit() {
+        assert!(Difficulty::custom(10, 10, 85).is_ok());
+        assert_eq!(
+            Difficulty::custom(10, 10, 86),
+            Err(DifficultyError::TooDense { max_mines: 85 })
+        );
+    }
+
+    #[test]
+    fn test_custom_difficulty_keeps_first_click_room() {
+        assert!(Difficulty::custom(5, 5, 16).is_ok());
+        assert_eq!(
+            Difficulty::custom(5, 5, 17),
+            Err(DifficultyError::TooDense { max_mines: 16 })
+        );
+    }
+
+    #[test]
+    fn test_builtin_difficulties_are_valid() {
+        assert!(Difficulty::Beginner.validate().is_ok());
+        assert!(Difficulty::Intermediate.validate().is_ok());
+        assert!(Difficulty::Expert.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mine_density_calculations() {
+        let (w1, h1, m1) = Difficulty::Beginner.get_dimensions();
+        let (w2, h2, m2) = Difficulty::Intermediate.get_dimensions();
+        let (w3, h3, m3) = Difficulty::Expert.get_dimensions();
+
+        let density1 = m1 as f64 / (w1 * h1) as f64;
+        let density2 = m2 as f64 / (w2 * h2) as f64;
+        let density3 = m3 as f64 / (w3 * h3) as f64;
+
+        assert!(density1 > 0.10 && density1 < 0.25);
+        assert!(density2 > 0.10 && density2 < 0.25);
+        assert!(density3 > 0.10 && density3 < 0.25);
+
+        assert!(density1 < density3);
+    }
+}
@@ -0,0 +1,691 @@
+use super::{Difficulty, SavedGame};
+use crate::debug::is_logging_enabled;
+use crate::game::{GameBoard, PlacementPolicy};
+use crate::replay::{Replay, ReplayAction};
+use crate::solver::{self, BoardView, Hint};
+use crate::timer::GameTimer;
+use log::debug;
+
+#[derive(Debug, Clone)]
+pub struct GameManager {
+    pub timer: GameTimer,
+    pub current_difficulty: Difficulty,
+    /// Only generate boards that can be cleared by logic alone.
+    pub no_guess: bool,
+    pub placement: PlacementPolicy,
+    /// Right-click cycles through a question mark after the flag.
+    pub question_marks: bool,
+    /// Fixed seed for every new board; `None` picks a fresh random seed per game.
+    pub seed: Option<u64>,
+    replay: Option<Replay>,
+}
+
+impl GameManager {
+    pub fn new() -> Self {
+        if is_logging_enabled() {
+            debug!("Creating new GameManager");
+        }
+        Self {
+            timer: GameTimer::new(),
+            current_difficulty: Difficulty::Beginner,
+            no_guess: false,
+            placement: PlacementPolicy::default(),
+            question_marks: false,
+            seed: None,
+            replay: None,
+        }
+    }
+
+    pub fn create_board(&self) -> GameBoard {
+        let (width, height, mines) = self.current_difficulty.get_dimensions();
+        let mut board = match self.seed {
+            Some(seed) => GameBoard::with_seed(width, height, mines, seed),
+            None => GameBoard::new(width, height, mines),
+        };
+        board.no_guess = self.no_guess;
+        board.placement = self.placement;
+        board.question_marks = self.question_marks;
+        board
+    }
+
+    pub fn start_game(&mut self, difficulty: Difficulty) {
+        if is_logging_enabled() {
+            debug!("Starting game with difficulty: {:?}", difficulty);
+        }
+        self.current_difficulty = difficulty;
+        self.replay = None;
+        self.timer.reset();
+        self.timer.start();
+    }
+
+    pub fn end_game(&mut self, won: bool) {
+        if is_logging_enabled() {
+            debug!("Ending game. Won: {}", won);
+        }
+        self.timer.pause();
+        let elapsed_ms = self.elapsed_ms();
+        if let Some(replay) = &mut self.replay {
+            replay.finish(won, elapsed_ms);
+        }
+    }
+
+    pub fn pause_game(&mut self) {
+        if is_logging_enabled() {
+            debug!("Pausing game");
+        }
+        if self.timer.is_running() {
+            self.record_timer_event(ReplayAction::Pause);
+        }
+        self.timer.pause();
+    }
+
+    pub fn resume_game(&mut self) {
+        if is_logging_enabled() {
+            debug!("Resuming game");
+        }
+        if !self.timer.is_running() {
+            self.record_timer_event(ReplayAction::Resume);
+        }
+        self.timer.start();
+    }
+
+    /// Reveals a cell, recording the click in the replay.
+    pub fn reveal_cell(&mut self, game_board: &mut GameBoard, x: usize, y: usize) -> bool {
+        self.record(game_board, ReplayAction::Reveal { x, y }, |board| {
+            board.reveal_cell(x, y)
+        })
+    }
+
+    /// Toggles a flag, recording the click in the replay.
+    pub fn toggle_flag(&mut self, game_board: &mut GameBoard, x: usize, y: usize) -> bool {
+        self.record(game_board, ReplayAction::Flag { x, y }, |board| {
+            board.toggle_flag(x, y)
+        })
+    }
+
+    /// Chords a number, recording the click in the replay.
+    pub fn chord(&mut self, game_board: &mut GameBoard, x: usize, y: usize) -> bool {
+        self.record(game_board, ReplayAction::Chord { x, y }, |board| {
+            board.chord(x, y)
+        })
+    }
+
+    /// Suggests a move for the board as the player sees it, counting the hint and marking
+    /// the game as assisted. `None` before the first click, while paused, once the game
+    /// is over, or when the solver has nothing to offer.
+    pub fn hint(&mut self, game_board: &mut GameBoard) -> Option<Hint> {
+        if !game_board.game_started
+            || game_board.first_click
+            || game_board.game_over
+            || !self.timer.is_running()
+        {
+            return None;
+        }
+        let hint = solver::hint(&BoardView::from_board(game_board))?;
+        if is_logging_enabled() {
+            debug!("Hint: {}", hint);
+        }
+        game_board.hints_used += 1;
+        game_board.assisted = true;
+        Some(hint)
+    }
+
+    /// Snapshot of `game_board` for resuming later, or `None` if no game is in progress.
+    pub fn save_game(&self, game_board: &GameBoard) -> Option<SavedGame> {
+        if !game_board.game_started || game_board.game_over {
+            return None;
+        }
+        Some(SavedGame {
+            difficulty: self.current_difficulty,
+            board: game_board.clone(),
+            elapsed: self.timer.get_elapsed(),
+            replay: self.replay.clone(),
+        })
+    }
+
+    /// Restores a saved game and returns its board. The timer is left paused at the
+    /// saved time so the player can look at the board before continuing.
+    pub fn restore_game(&mut self, saved: SavedGame) -> GameBoard {
+        if is_logging_enabled() {
+            debug!(
+                "Restoring saved {:?} game at {:?}",
+                saved.difficulty, saved.elapsed
+            );
+        }
+        self.current_difficulty = saved.difficulty;
+        self.timer = GameTimer::with_elapsed(saved.elapsed);
+        self.replay = saved.replay;
+        let mut board = saved.board;
+        board.game_started = true;
+        board
+    }
+
+    /// Applies any recordable action, dispatching to the matching method above.
+    pub fn apply_action(&mut self, game_board: &mut GameBoard, action: ReplayAction) -> bool {
+        match action {
+            ReplayAction::Reveal { x, y } => self.reveal_cell(game_board, x, y),
+            ReplayAction::Flag { x, y } => self.toggle_flag(game_board, x, y),
+            ReplayAction::Chord { x, y } => self.chord(game_board, x, y),
+            ReplayAction::Undo => self.undo_move(game_board),
+            ReplayAction::Redo => self.redo_move(game_board),
+            ReplayAction::Pause => {
+                self.pause_game();
+                true
+            }
+            ReplayAction::Resume => {
+                self.resume_game();
+                true
+            }
+        }
+    }
+
+    /// The recording of the current game, if any input has been made yet.
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.timer.get_elapsed().as_millis() as u64
+    }
+
+    fn record(
+        &mut self,
+        game_board: &mut GameBoard,
+        action: ReplayAction,
+        apply: impl FnOnce(&mut GameBoard) -> bool,
+    ) -> bool {
+        let time_ms = self.elapsed_ms();
+        let replay = self.replay.get_or_insert_with(|| Replay::new(game_board));
+        let changed = apply(game_board);
+        replay.record(time_ms, action);
+        replay.sync_layout(game_board);
+        Self::count_click(game_board, action, changed);
+        changed
+    }
+
+    /// Board clicks count toward the efficiency stats; undo and redo don't.
+    fn count_click(game_board: &mut GameBoard, action: ReplayAction, changed: bool) {
+        let clicks = &mut game_board.clicks;
+        match action {
+            ReplayAction::Reveal { .. } => clicks.left += 1,
+            ReplayAction::Flag { .. } => clicks.right += 1,
+            ReplayAction::Chord { .. } => clicks.chord += 1,
+            _ => return,
+        }
+        if changed {
+            clicks.effective += 1;
+        }
+    }
+
+    /// Pause and resume only matter once there is a recording to add them to.
+    fn record_timer_event(&mut self, action: ReplayAction) {
+        let time_ms = self.elapsed_ms();
+        if let Some(replay) = &mut self.replay {
+            replay.record(time_ms, action);
+        }
+    }
+
+    /// Undoes the last move, restarting the timer if that takes back a finished game.
+    pub fn undo_move(&mut self, game_board: &mut GameBoard) -> bool {
+        let was_over = game_board.game_over;
+        if !self.record(game_board, ReplayAction::Undo, GameBoard::undo) {
+            return false;
+        }
+        if was_over && !game_board.game_over {
+            if is_logging_enabled() {
+                debug!("Undo reopened a finished game, resuming timer");
+            }
+            if let Some(replay) = &mut self.replay {
+                replay.won = None;
+            }
+            self.timer.start();
+        }
+        true
+    }
+
+    pub fn redo_move(&mut self, game_board: &mut GameBoard) -> bool {
+        self.record(game_board, ReplayAction::Redo, GameBoard::redo)
+    }
+
+    /// Starts a fresh board at the current difficulty. A board loaded from a file can be
+    /// outside the custom limits, and then the new game falls back to Beginner.
+    pub fn reset_game(&mut self, game_board: &mut GameBoard) {
+        if let Err(e) = self.current_difficulty.validate() {
+            if is_logging_enabled() {
+                debug!(
+                    "Can't start a new {:?} game ({}), using Beginner",
+                    self.current_difficulty, e
+                );
+            }
+            self.current_difficulty = Difficulty::Beginner;
+        }
+        if is_logging_enabled() {
+            debug!(
+                "Resetting game with current difficulty: {:?}",
+                self.current_difficulty
+            );
+        }
+        *game_board = self.create_board();
+        self.replay = None;
+        self.timer.reset();
+    }
+}
+
+impl Default for GameManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Clicks;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_game_manager_initialization() {
+        let manager = GameManager::new();
+
+        assert_eq!(manager.current_difficulty, Difficulty::Beginner);
+        assert!(!manager.timer.is_running());
+        assert_eq!(manager.timer.get_elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_game_manager_default() {
+        let manager = GameManager::default();
+
+        assert_eq!(manager.current_difficulty, Difficulty::Beginner);
+        assert!(!manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_start_game() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Expert);
+
+        assert_eq!(manager.current_difficulty, Difficulty::Expert);
+        assert!(manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_start_game_different_difficulties() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Beginner);
+        assert_eq!(manager.current_difficulty, Difficulty::Beginner);
+        assert!(manager.timer.is_running());
+
+        manager.start_game(Difficulty::Intermediate);
+        assert_eq!(manager.current_difficulty, Difficulty::Intermediate);
+        assert!(manager.timer.is_running());
+
+        manager.start_game(Difficulty::Expert);
+        assert_eq!(manager.current_difficulty, Difficulty::Expert);
+        assert!(manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_end_game_won() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Beginner);
+        assert!(manager.timer.is_running());
+
+        manager.end_game(true);
+        assert!(!manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_end_game_lost() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Beginner);
+        assert!(manager.timer.is_running());
+
+        manager.end_game(false);
+        assert!(!manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_pause_and_resume_game() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Beginner);
+        assert!(manager.timer.is_running());
+
+        manager.pause_game();
+        assert!(!manager.timer.is_running());
+
+        manager.resume_game();
+        assert!(manager.timer.is_running());
+    }
+
+    #[test]
+    fn test_reset_game() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+
+        manager.start_game(Difficulty::Expert);
+        game_board.start_game();
+        game_board.reveal_cell(0, 0);
+
+        assert!(game_board.game_started);
+        assert!(!game_board.first_click);
+
+        manager.reset_game(&mut game_board);
+
+        assert_eq!(manager.current_difficulty, Difficulty::Expert);
+        assert!(!manager.timer.is_running());
+        assert_eq!(manager.timer.get_elapsed(), Duration::ZERO);
+
+        assert!(!game_board.game_started);
+        assert!(game_board.first_click);
+        assert!(!game_board.game_over);
+        assert!(!game_board.game_won);
+    }
+
+    #[test]
+    fn test_reset_game_different_difficulty() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+
+        manager.current_difficulty = Difficulty::Expert;
+
+        manager.reset_game(&mut game_board);
+
+        let (width, height, mines) = Difficulty::Expert.get_dimensions();
+        assert_eq!(game_board.width, width);
+        assert_eq!(game_board.height, height);
+        assert_eq!(game_board.mine_count, mines);
+    }
+
+    #[test]
+    fn test_reset_game_falls_back_from_invalid_custom() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(4, 4, 12);
+
+        manager.current_difficulty = Difficulty::Custom {
+            width: 4,
+            height: 4,
+            mines: 12,
+        };
+        manager.reset_game(&mut game_board);
+
+        assert_eq!(manager.current_difficulty, Difficulty::Beginner);
+        assert_eq!((game_board.width, game_board.height), (9, 9));
+    }
+
+    #[test]
+    fn test_reset_game_with_fixed_seed() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+
+        manager.seed = Some(1234);
+        manager.reset_game(&mut game_board);
+        assert_eq!(game_board.seed, 1234);
+
+        manager.reset_game(&mut game_board);
+        assert_eq!(game_board.seed, 1234);
+    }
+
+    #[test]
+    fn test_reset_game_keeps_no_guess_mode() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+
+        manager.no_guess = true;
+        manager.reset_game(&mut game_board);
+
+        assert!(game_board.no_guess);
+    }
+
+    #[test]
+    fn test_reset_game_keeps_placement_policy() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+
+        manager.placement = PlacementPolicy::SafeCell;
+        manager.reset_game(&mut game_board);
+
+        assert_eq!(game_board.placement, PlacementPolicy::SafeCell);
+    }
+
+    #[test]
+    fn test_undo_move_resumes_timer_after_loss() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+        manager.placement = PlacementPolicy::None;
+        game_board.placement = PlacementPolicy::None;
+        game_board.mine_count = 81;
+
+        manager.start_game(Difficulty::Beginner);
+        game_board.start_game();
+        game_board.reveal_cell(0, 0);
+        assert!(game_board.game_over);
+        manager.end_game(false);
+
+        assert!(manager.undo_move(&mut game_board));
+        assert!(!game_board.game_over);
+        assert!(manager.timer.is_running());
+        assert!(game_board.assisted);
+    }
+
+    #[test]
+    fn test_undo_move_without_history() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::new(9, 9, 10);
+        game_board.start_game();
+
+        assert!(!manager.undo_move(&mut game_board));
+        assert!(!manager.redo_move(&mut game_board));
+        assert!(!game_board.assisted);
+    }
+
+    #[test]
+    fn test_inputs_are_recorded_in_replay() {
+        let mut manager = GameManager::new();
+        manager.seed = Some(21);
+        let mut game_board = manager.create_board();
+
+        manager.start_game(Difficulty::Beginner);
+        game_board.start_game();
+        assert!(manager.replay().is_none());
+
+        manager.reveal_cell(&mut game_board, 4, 4);
+        let (mx, my) = game_board.mine_positions()[0];
+        manager.toggle_flag(&mut game_board, mx, my);
+        manager.pause_game();
+        manager.pause_game();
+        manager.resume_game();
+        manager.chord(&mut game_board, 4, 4);
+
+        let replay = manager.replay().unwrap();
+        let actions: Vec<ReplayAction> = replay.events.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ReplayAction::Reveal { x: 4, y: 4 },
+                ReplayAction::Flag { x: mx, y: my },
+                ReplayAction::Pause,
+                ReplayAction::Resume,
+                ReplayAction::Chord { x: 4, y: 4 },
+            ]
+        );
+        assert!(replay
+            .events
+            .windows(2)
+            .all(|w| w[0].time_ms <= w[1].time_ms));
+        assert_eq!(replay.seed, 21);
+        assert_eq!(replay.layouts.len(), 1);
+        assert_eq!(replay.layouts[0].mines, game_board.mine_positions());
+    }
+
+    #[test]
+    fn test_apply_action_dispatches_and_records() {
+        let mut manager = GameManager::new();
+        manager.seed = Some(3);
+        let mut game_board = manager.create_board();
+
+        manager.start_game(Difficulty::Beginner);
+        game_board.start_game();
+        assert!(manager.apply_action(&mut game_board, ReplayAction::Reveal { x: 4, y: 4 }));
+        assert!(!game_board.first_click);
+        assert!(manager.apply_action(&mut game_board, ReplayAction::Pause));
+        assert!(!manager.timer.is_running());
+
+        assert_eq!(manager.replay().unwrap().events.len(), 2);
+    }
+
+    #[test]
+    fn test_board_clicks_are_counted() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::from_ascii("*.\n..").unwrap();
+        game_board.start_game();
+
+        manager.reveal_cell(&mut game_board, 1, 1);
+        manager.reveal_cell(&mut game_board, 1, 1);
+        manager.toggle_flag(&mut game_board, 0, 0);
+        manager.chord(&mut game_board, 1, 1);
+        manager.undo_move(&mut game_board);
+
+        let expected = Clicks {
+            left: 2,
+            right: 1,
+            chord: 1,
+            effective: 3,
+        };
+        assert_eq!(game_board.clicks, expected);
+    }
+
+    #[test]
+    fn test_save_and_restore_game() {
+        let mut manager = GameManager::new();
+        manager.current_difficulty = Difficulty::Intermediate;
+        let mut game_board = manager.create_board();
+
+        assert!(manager.save_game(&game_board).is_none());
+
+        manager.start_game(Difficulty::Intermediate);
+        game_board.start_game();
+        manager.reveal_cell(&mut game_board, 8, 8);
+        thread::sleep(Duration::from_millis(5));
+        let saved = manager.save_game(&game_board).unwrap();
+
+        let mut other = GameManager::new();
+        let restored = other.restore_game(saved.clone());
+
+        assert_eq!(other.current_difficulty, Difficulty::Intermediate);
+        assert!(!other.timer.is_running());
+        assert_eq!(other.timer.get_elapsed(), saved.elapsed);
+        assert!(other.timer.get_elapsed() >= Duration::from_millis(5));
+        assert!(restored.game_started);
+        assert_eq!(restored.board, game_board.board);
+        assert_eq!(other.replay(), manager.replay());
+    }
+
+    #[test]
+    fn test_finished_game_is_not_saved() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        game_board.start_game();
+        manager.reveal_cell(&mut game_board, 0, 0);
+
+        assert!(game_board.game_over);
+        assert!(manager.save_game(&game_board).is_none());
+    }
+
+    #[test]
+    fn test_hint_counts_and_marks_assisted() {
+        let mut manager = GameManager::new();
+        let mut game_board = GameBoard::from_ascii("*1.\n11.\n...").unwrap();
+        game_board.start_game();
+
+        // Not while the timer is stopped
+        assert!(manager.hint(&mut game_board).is_none());
+
+        manager.start_game(Difficulty::Beginner);
+        let hint = manager.hint(&mut game_board).unwrap();
+        assert!(matches!(hint, Hint::Safe { .. }));
+        manager.hint(&mut game_board);
+        assert_eq!(game_board.hints_used, 2);
+        assert!(game_board.assisted);
+
+        manager.reset_game(&mut game_board);
+        assert_eq!(game_board.hints_used, 0);
+    }
+
+    #[test]
+    fn test_end_game_finishes_replay() {
+        let mut manager = GameManager::new();
+        let mut game_board = manager.create_board();
+
+        manager.start_game(Difficulty::Beginner);
+        game_board.start_game();
+        manager.reveal_cell(&mut game_board, 0, 0);
+        manager.end_game(true);
+
+        assert_eq!(manager.replay().unwrap().won, Some(true));
+    }
+
+    #[test]
+    fn test_new_game_clears_replay() {
+        let mut manager = GameManager::new();
+        let mut game_board = manager.create_board();
+
+        manager.start_game(Difficulty::Beginner);
+        game_board.start_game();
+        manager.reveal_cell(&mut game_board, 0, 0);
+        manager.reset_game(&mut game_board);
+        assert!(manager.replay().is_none());
+
+        game_board.start_game();
+        manager.reveal_cell(&mut game_board, 0, 0);
+        manager.start_game(Difficulty::Beginner);
+        assert!(manager.replay().is_none());
+    }
+
+    #[test]
+    fn test_timer_integration() {
+        let mut manager = GameManager::new();
+
+        manager.start_game(Difficulty::Beginner);
+        thread::sleep(Duration::from_millis(10));
+
+        let elapsed_before_pause = manager.timer.get_elapsed();
+        assert!(elapsed_before_pause > Duration::ZERO);
+
+        manager.pause_game();
+        thread::sleep(Duration::from_millis(10));
+        let elapsed_after_pause = manager.timer.get_elapsed();
+        let time_diff =
+            elapsed_after_pause.as_millis() as i64 - elapsed_before_pause.as_millis() as i64;
+        assert!(time_diff.abs() < 5);
+
+        manager.resume_game();
+        thread::sleep(Duration::from_millis(10));
+        let elapsed_after_resume = manager.timer.get_elapsed();
+        assert!(elapsed_after_resume > elapsed_after_pause);
+    }
+
+    #[test]
+    fn test_game_manager_clone() {
+        let mut manager = GameManager::new();
+        manager.start_game(Difficulty::Intermediate);
+
+        let cloned = manager.clone();
+        assert_eq!(manager.current_difficulty, cloned.current_difficulty);
+        assert_eq!(manager.timer.is_running(), cloned.timer.is_running());
+    }
+
+    #[test]
+    fn test_game_manager_debug() {
+        let manager = GameManager::new();
+        let debug_str = format!("{:?}", manager);
+        assert!(debug_str.contains("GameManager"));
+        assert!(debug_str.contains("Beginner"));
+    }
+}
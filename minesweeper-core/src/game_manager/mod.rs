@@ -1,5 +1,7 @@
 pub mod difficulty;
 mod manager;
+pub mod saved_game;
 
 pub use difficulty::Difficulty;
 pub use manager::GameManager;
+pub use saved_game::{SavedGame, SavedGameError};
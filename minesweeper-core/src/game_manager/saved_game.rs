@@ -0,0 +1,444 @@
+//! Snapshot of an unfinished game, stored as plain text:
+//!
+//! ```text
+//! minesweeper-save 1
+//! difficulty beginner
+//! size 9 9 10
+//! seed 1234
+//! placement safe-opening
+//! no-guess false
+//! question-marks false
+//! assisted false
+//! hints 0
+//! clicks 12 3 2 16
+//! elapsed-ms 48210
+//! board
+//! H.H.R1F*...
+//! ...
+//! replay
+//! minesweeper-replay 2
+//! ...
+//! ```
+//!
+//! `difficulty` is `beginner`, `intermediate`, `expert` or `custom`. Each board row has
+//! two characters per cell: the state (`H`idden, `R`evealed, `F`lagged, `Q`uestioned)
+//! then the content (`.` empty, `*` mine, `1`-`8`). The optional `replay` section holds
+//! the game's recording so far in the replay file format. `clicks` gives the left, right,
+//! chord and effective click counts. `hints` and `clicks` may be missing from older
+//! saves and then read as 0.
+
+use super::Difficulty;
+use crate::game::{Cell, CellContent, CellState, Clicks, GameBoard, LayoutError, PlacementPolicy};
+use crate::replay::{Replay, ReplayParseError};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+const HEADER: &str = "minesweeper-save 1";
+
+/// An in-progress game: the full board, the clock and the recording so far.
+#[derive(Debug, Clone)]
+pub struct SavedGame {
+    pub difficulty: Difficulty,
+    pub board: GameBoard,
+    pub elapsed: Duration,
+    pub replay: Option<Replay>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SavedGameError {
+    Parse { line: usize, message: String },
+    Layout(LayoutError),
+    Replay(ReplayParseError),
+}
+
+impl fmt::Display for SavedGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SavedGameError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            SavedGameError::Layout(e) => write!(f, "invalid board: {}", e),
+            SavedGameError::Replay(e) => write!(f, "invalid replay: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SavedGameError {}
+
+impl fmt::Display for SavedGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let board = &self.board;
+        writeln!(f, "{}", HEADER)?;
+        let difficulty = match self.difficulty {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Expert => "expert",
+            Difficulty::Custom { .. } => "custom",
+        };
+        writeln!(f, "difficulty {}", difficulty)?;
+        writeln!(
+            f,
+            "size {} {} {}",
+            board.width, board.height, board.mine_count
+        )?;
+        writeln!(f, "seed {}", board.seed)?;
+        writeln!(f, "placement {}", board.placement)?;
+        writeln!(f, "no-guess {}", board.no_guess)?;
+        writeln!(f, "question-marks {}", board.question_marks)?;
+        writeln!(f, "assisted {}", board.assisted)?;
+        writeln!(f, "hints {}", board.hints_used)?;
+        let clicks = board.clicks;
+        writeln!(
+            f,
+            "clicks {} {} {} {}",
+            clicks.left, clicks.right, clicks.chord, clicks.effective
+        )?;
+        writeln!(f, "elapsed-ms {}", self.elapsed.as_millis())?;
+        writeln!(f, "board")?;
+        for row in &board.board {
+            let line: String = row.iter().flat_map(|cell| encode_cell(*cell)).collect();
+            writeln!(f, "{}", line)?;
+        }
+        if let Some(replay) = &self.replay {
+            writeln!(f, "replay")?;
+            write!(f, "{}", replay)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SavedGame {
+    type Err = SavedGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().map(str::trim).collect();
+        if lines.first() != Some(&HEADER) {
+            return Err(parse_error(1, "not a minesweeper save file"));
+        }
+
+        let mut values = std::collections::HashMap::new();
+        let mut index = 1;
+        while index < lines.len() && lines[index] != "board" {
+            let text = lines[index];
+            if !text.is_empty() {
+                let (key, value) = text.split_once(' ').unwrap_or((text, ""));
+                values.insert(key, (index + 1, value));
+            }
+            index += 1;
+        }
+        let board_line = index + 1;
+        if index == lines.len() {
+            return Err(parse_error(board_line, "missing 'board' section"));
+        }
+
+        let value = |key: &str| {
+            values
+                .get(key)
+                .copied()
+                .ok_or_else(|| parse_error(board_line, &format!("missing '{}' line", key)))
+        };
+        let parse = |key: &str| -> Result<u64, SavedGameError> {
+            let (line, text) = value(key)?;
+            text.parse()
+                .map_err(|_| parse_error(line, &format!("invalid {} '{}'", key, text)))
+        };
+        let flag = |key: &str| -> Result<bool, SavedGameError> {
+            let (line, text) = value(key)?;
+            text.parse()
+                .map_err(|_| parse_error(line, &format!("invalid {} '{}'", key, text)))
+        };
+
+        let (size_line, size) = value("size")?;
+        let dimensions: Vec<usize> = size
+            .split_whitespace()
+            .map(|n| n.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| parse_error(size_line, "invalid size"))?;
+        let [width, height, mine_count] = dimensions[..] else {
+            return Err(parse_error(
+                size_line,
+                "size needs width, height and mine count",
+            ));
+        };
+
+        let (difficulty_line, difficulty) = value("difficulty")?;
+        let difficulty = match difficulty {
+            "beginner" => Difficulty::Beginner,
+            "intermediate" => Difficulty::Intermediate,
+            "expert" => Difficulty::Expert,
+            "custom" => Difficulty::Custom {
+                width,
+                height,
+                mines: mine_count,
+            },
+            other => {
+                return Err(parse_error(
+                    difficulty_line,
+                    &format!("unknown difficulty '{}'", other),
+                ))
+            }
+        };
+        if difficulty.get_dimensions() != (width, height, mine_count) {
+            return Err(parse_error(size_line, "size does not match the difficulty"));
+        }
+
+        let (placement_line, placement) = value("placement")?;
+        let placement: PlacementPolicy = placement
+            .parse()
+            .map_err(|e: String| parse_error(placement_line, &e))?;
+
+        let replay_index = lines[index + 1..]
+            .iter()
+            .position(|line| *line == "replay")
+            .map(|offset| index + 1 + offset);
+        let rows_end = replay_index.unwrap_or(lines.len());
+
+        let mut cells = Vec::with_capacity(height);
+        for (offset, text) in lines[index + 1..rows_end].iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = board_line + 1 + offset;
+            let chars: Vec<char> = text.chars().collect();
+            let pairs = chars.chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return Err(parse_error(line, "row has a dangling character"));
+            }
+            let row = pairs
+                .map(|pair| decode_cell(pair[0], pair[1]))
+                .collect::<Option<Vec<Cell>>>()
+                .ok_or_else(|| parse_error(line, "invalid cell"))?;
+            cells.push(row);
+        }
+        if cells.len() != height || cells.first().map_or(0, |row| row.len()) != width {
+            return Err(parse_error(
+                board_line,
+                &format!("board is not {}x{}", width, height),
+            ));
+        }
+
+        let mut board = GameBoard::from_cells(cells, mine_count).map_err(SavedGameError::Layout)?;
+        if board.game_over {
+            return Err(parse_error(board_line, "board holds a finished game"));
+        }
+        board.seed = parse("seed")?;
+        board.placement = placement;
+        board.no_guess = flag("no-guess")?;
+        board.question_marks = flag("question-marks")?;
+        board.assisted = flag("assisted")?;
+        if values.contains_key("hints") {
+            board.hints_used = parse("hints")? as usize;
+        }
+        if let Some(&(clicks_line, text)) = values.get("clicks") {
+            let counts: Vec<usize> = text
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| parse_error(clicks_line, "invalid clicks"))?;
+            let [left, right, chord, effective] = counts[..] else {
+                return Err(parse_error(
+                    clicks_line,
+                    "clicks needs left, right, chord and effective counts",
+                ));
+            };
+            board.clicks = Clicks {
+                left,
+                right,
+                chord,
+                effective,
+            };
+        }
+
+        let replay = match replay_index {
+            Some(replay_index) => Some(
+                lines[replay_index + 1..]
+                    .join("\n")
+                    .parse::<Replay>()
+                    .map_err(|e| {
+                        SavedGameError::Replay(ReplayParseError {
+                            line: e.line + replay_index + 1,
+                            message: e.message,
+                        })
+                    })?,
+            ),
+            None => None,
+        };
+
+        Ok(SavedGame {
+            difficulty,
+            board,
+            elapsed: Duration::from_millis(parse("elapsed-ms")?),
+            replay,
+        })
+    }
+}
+
+fn parse_error(line: usize, message: &str) -> SavedGameError {
+    SavedGameError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn encode_cell(cell: Cell) -> [char; 2] {
+    let state = match cell.state {
+        CellState::Hidden => 'H',
+        CellState::Revealed => 'R',
+        CellState::Flagged => 'F',
+        CellState::Questioned => 'Q',
+    };
+    let content = match cell.content {
+        CellContent::Empty => '.',
+        CellContent::Mine => '*',
+        CellContent::Number(n) => char::from_digit(n as u32, 10).unwrap_or('?'),
+    };
+    [state, content]
+}
+
+fn decode_cell(state: char, content: char) -> Option<Cell> {
+    let state = match state {
+        'H' => CellState::Hidden,
+        'R' => CellState::Revealed,
+        'F' => CellState::Flagged,
+        'Q' => CellState::Questioned,
+        _ => return None,
+    };
+    let content = match content {
+        '.' => CellContent::Empty,
+        '*' => CellContent::Mine,
+        '1'..='8' => CellContent::Number(content.to_digit(10)? as u8),
+        _ => return None,
+    };
+    Some(Cell { state, content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReplayAction;
+
+    fn in_progress() -> SavedGame {
+        let mut board = GameBoard::with_seed(9, 9, 10, 77);
+        board.question_marks = true;
+        board.start_game();
+        board.hints_used = 2;
+        board.clicks = Clicks {
+            left: 4,
+            right: 1,
+            chord: 2,
+            effective: 6,
+        };
+        let mut replay = Replay::new(&board);
+        board.reveal_cell(4, 4);
+        replay.record(0, ReplayAction::Reveal { x: 4, y: 4 });
+        replay.sync_layout(&board);
+        let (mx, my) = board.mine_positions()[0];
+        board.toggle_flag(mx, my);
+        replay.record(800, ReplayAction::Flag { x: mx, y: my });
+
+        SavedGame {
+            difficulty: Difficulty::Beginner,
+            board,
+            elapsed: Duration::from_millis(1234),
+            replay: Some(replay),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let saved = in_progress();
+        let loaded: SavedGame = saved.to_string().parse().unwrap();
+
+        assert_eq!(loaded.difficulty, Difficulty::Beginner);
+        assert_eq!(loaded.board.board, saved.board.board);
+        assert_eq!(loaded.board.seed, 77);
+        assert!(loaded.board.question_marks);
+        assert_eq!(loaded.board.flags_placed(), 1);
+        assert_eq!(loaded.board.hints_used, 2);
+        assert_eq!(loaded.board.clicks, saved.board.clicks);
+        assert_eq!(loaded.elapsed, Duration::from_millis(1234));
+        assert_eq!(loaded.replay, saved.replay);
+    }
+
+    #[test]
+    fn test_round_trip_custom_before_first_click() {
+        let saved = SavedGame {
+            difficulty: Difficulty::Custom {
+                width: 12,
+                height: 7,
+                mines: 20,
+            },
+            board: GameBoard::with_seed(12, 7, 20, 5),
+            elapsed: Duration::ZERO,
+            replay: None,
+        };
+        let loaded: SavedGame = saved.to_string().parse().unwrap();
+
+        assert_eq!(loaded.difficulty, saved.difficulty);
+        assert!(loaded.board.first_click);
+        assert_eq!(loaded.replay, None);
+    }
+
+    #[test]
+    fn test_rejects_size_that_does_not_match_difficulty() {
+        let text = in_progress()
+            .to_string()
+            .replace("difficulty beginner", "difficulty expert");
+        assert!(matches!(
+            text.parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Parse { line: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_bad_board() {
+        let text = in_progress().to_string();
+        let mut lines: Vec<&str> = text.lines().collect();
+        let board_index = lines.iter().position(|l| *l == "board").unwrap();
+        lines[board_index + 2] = "H.H.";
+        assert!(matches!(
+            lines.join("\n").parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Layout(LayoutError::RowLength { y: 1, .. })
+        ));
+        lines.truncate(board_index + 3);
+        assert!(matches!(
+            lines.join("\n").parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Parse { .. }
+        ));
+
+        let wrong_mines = text.replacen("H*", "H.", 1);
+        assert!(matches!(
+            wrong_mines.parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Layout(LayoutError::MineCountMismatch { .. })
+        ));
+
+        let revealed_mine = text.replacen("H*", "R*", 1);
+        assert!(matches!(
+            revealed_mine.parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Parse { .. }
+        ));
+    }
+
+    #[test]
+    fn test_hints_and_clicks_lines_are_optional() {
+        let text = in_progress()
+            .to_string()
+            .replace("hints 2\n", "")
+            .replace("clicks 4 1 2 6\n", "");
+        let loaded: SavedGame = text.parse().unwrap();
+        assert_eq!(loaded.board.hints_used, 0);
+        assert_eq!(loaded.board.clicks, Clicks::default());
+
+        let short = in_progress()
+            .to_string()
+            .replace("clicks 4 1 2 6", "clicks 4 1");
+        assert!(matches!(
+            short.parse::<SavedGame>().unwrap_err(),
+            SavedGameError::Parse { .. }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_wrong_header() {
+        assert!("minesweeper-replay 1\n".parse::<SavedGame>().is_err());
+    }
+}
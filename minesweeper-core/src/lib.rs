@@ -0,0 +1,29 @@
+//! The Minesweeper rules engine, independent of any frontend.
+//!
+//! Build a [`GameBoard`] from a [`Difficulty`], drive it with
+//! [`GameBoard::reveal_cell`], [`GameBoard::toggle_flag`] and [`GameBoard::chord`],
+//! and time it with a [`GameManager`], which also records a [`Replay`] of every input.
+//!
+//! The `serde` feature adds `Serialize`/`Deserialize` for the board types and a
+//! versioned JSON board file format; see `game::board_file`.
+
+pub mod debug;
+pub mod game;
+pub mod game_manager;
+pub mod replay;
+pub mod solver;
+pub mod timer;
+
+pub use game::{
+    AsciiBoardError, Cell, CellContent, CellState, Clicks, GameBoard, GameStats, LayoutError,
+    LosingClick, PlacementPolicy, PostMortem,
+};
+#[cfg(feature = "serde")]
+pub use game::{BoardFile, BoardFileError, GameStatus};
+pub use game_manager::{Difficulty, GameManager, SavedGame, SavedGameError};
+pub use replay::{Replay, ReplayAction, ReplayEvent, ReplayLayout, ReplayParseError, ReplayPlayer};
+pub use solver::{
+    autoplay, deduce, hint, mine_probabilities, AutoplayOutcome, BoardView, Deductions, Hint,
+    Probabilities, Reason, VisibleCell,
+};
+pub use timer::GameTimer;
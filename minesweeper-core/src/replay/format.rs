@@ -0,0 +1,518 @@
+//! Plain-text replay files.
+//!
+//! ```text
+//! minesweeper-replay 2
+//! size 9 9 10
+//! seed 1234
+//! placement safe-opening
+//! no-guess false
+//! question-marks false
+//! layout 0 0,3 5,1 ...
+//! result won 48210
+//! events
+//! 0 reveal 4 4
+//! 1830 flag 2 3
+//! 2400 chord 3 3
+//! 5000 pause
+//! ```
+//!
+//! Each `layout` line is the index of the first event played on it followed by the mine
+//! positions; there is one per layout the game went through, and none before the first
+//! reveal. Version 1 files have a single `mines` line instead, read as a layout from the
+//! first event. `result` is `won <ms>`, `lost <ms>` or `unfinished`. Each event line is
+//! the clock time in milliseconds followed by `reveal`, `flag` or `chord` with `x y`, or
+//! one of `undo`, `redo`, `pause` and `resume`. Times never go backwards.
+
+use super::recording::{Replay, ReplayAction, ReplayEvent, ReplayLayout};
+use crate::game::PlacementPolicy;
+use crate::game_manager::difficulty::MAX_CUSTOM_DIMENSION;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+const HEADER: &str = "minesweeper-replay 2";
+/// Older header, still read; its `mines` line holds the final layout.
+const HEADER_V1: &str = "minesweeper-replay 1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayParseError {
+    /// 1-based line number the error was found on.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ReplayParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ReplayParseError {}
+
+impl fmt::Display for ReplayAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayAction::Reveal { x, y } => write!(f, "reveal {} {}", x, y),
+            ReplayAction::Flag { x, y } => write!(f, "flag {} {}", x, y),
+            ReplayAction::Chord { x, y } => write!(f, "chord {} {}", x, y),
+            ReplayAction::Undo => f.write_str("undo"),
+            ReplayAction::Redo => f.write_str("redo"),
+            ReplayAction::Pause => f.write_str("pause"),
+            ReplayAction::Resume => f.write_str("resume"),
+        }
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "size {} {} {}", self.width, self.height, self.mine_count)?;
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "placement {}", self.placement)?;
+        writeln!(f, "no-guess {}", self.no_guess)?;
+        writeln!(f, "question-marks {}", self.question_marks)?;
+        for layout in &self.layouts {
+            let mines: Vec<String> = layout
+                .mines
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect();
+            writeln!(f, "layout {} {}", layout.from_event, mines.join(" "))?;
+        }
+        match self.won {
+            Some(true) => writeln!(f, "result won {}", self.duration_ms)?,
+            Some(false) => writeln!(f, "result lost {}", self.duration_ms)?,
+            None => writeln!(f, "result unfinished")?,
+        }
+        writeln!(f, "events")?;
+        for event in &self.events {
+            writeln!(f, "{} {}", event.time_ms, event.action)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Replay {
+    type Err = ReplayParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line.trim()));
+
+        let legacy = match lines.next().map(|(_, text)| text) {
+            Some(HEADER) => false,
+            Some(HEADER_V1) => true,
+            _ => return Err(error(1, "not a minesweeper replay file")),
+        };
+
+        let mut size = None;
+        let mut seed = None;
+        let mut placement = None;
+        let mut no_guess = None;
+        let mut question_marks = None;
+        let mut layouts: Vec<(usize, ReplayLayout)> = Vec::new();
+        let mut result = None;
+        let mut last_line = 1;
+
+        for (line, text) in lines.by_ref() {
+            last_line = line;
+            if text.is_empty() {
+                continue;
+            }
+            if text == "events" {
+                break;
+            }
+            let (key, value) = text.split_once(' ').unwrap_or((text, ""));
+            match key {
+                "size" => {
+                    let numbers = parse_numbers(line, value)?;
+                    match numbers[..] {
+                        [width, height, mine_count] => {
+                            check_size(line, width, height, mine_count)?;
+                            size = Some((width, height, mine_count));
+                        }
+                        _ => return Err(error(line, "size needs width, height and mine count")),
+                    }
+                }
+                "seed" => seed = Some(parse_value(line, value, "seed")?),
+                "placement" => {
+                    placement = Some(
+                        value
+                            .parse::<PlacementPolicy>()
+                            .map_err(|e| error(line, &e))?,
+                    )
+                }
+                "no-guess" => no_guess = Some(parse_value(line, value, "no-guess")?),
+                "question-marks" => {
+                    question_marks = Some(parse_value(line, value, "question-marks")?)
+                }
+                "mines" if legacy => {
+                    let mines = parse_mines(line, value)?;
+                    if !mines.is_empty() {
+                        layouts.push((
+                            line,
+                            ReplayLayout {
+                                from_event: 0,
+                                mines,
+                            },
+                        ));
+                    }
+                }
+                "layout" if !legacy => {
+                    let (from_event, mines) = value.split_once(' ').unwrap_or((value, ""));
+                    let from_event = parse_value(line, from_event, "layout event")?;
+                    if layouts
+                        .last()
+                        .is_some_and(|(_, l)| l.from_event >= from_event)
+                    {
+                        return Err(error(line, "layouts must be in event order"));
+                    }
+                    let mines = parse_mines(line, mines)?;
+                    layouts.push((line, ReplayLayout { from_event, mines }));
+                }
+                "result" => result = Some(parse_result(line, value)?),
+                _ => return Err(error(line, &format!("unknown key '{}'", key))),
+            }
+        }
+
+        let missing = |key: &str| error(last_line, &format!("missing '{}' line", key));
+        let (width, height, mine_count) = size.ok_or_else(|| missing("size"))?;
+        let (won, duration_ms) = result.ok_or_else(|| missing("result"))?;
+        for (line, layout) in &layouts {
+            check_layout(*line, &layout.mines, width, height, mine_count)?;
+        }
+
+        let mut events: Vec<ReplayEvent> = Vec::new();
+        for (line, text) in lines {
+            if text.is_empty() {
+                continue;
+            }
+            let event = parse_event(line, text)?;
+            if events
+                .last()
+                .is_some_and(|last| last.time_ms > event.time_ms)
+            {
+                return Err(error(line, "event time goes backwards"));
+            }
+            events.push(event);
+        }
+
+        Ok(Replay {
+            width,
+            height,
+            mine_count,
+            seed: seed.ok_or_else(|| missing("seed"))?,
+            placement: placement.ok_or_else(|| missing("placement"))?,
+            no_guess: no_guess.ok_or_else(|| missing("no-guess"))?,
+            question_marks: question_marks.ok_or_else(|| missing("question-marks"))?,
+            layouts: layouts.into_iter().map(|(_, layout)| layout).collect(),
+            events,
+            won,
+            duration_ms,
+        })
+    }
+}
+
+fn error(line: usize, message: &str) -> ReplayParseError {
+    ReplayParseError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn parse_value<T: FromStr>(line: usize, value: &str, what: &str) -> Result<T, ReplayParseError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| error(line, &format!("invalid {} '{}'", what, value)))
+}
+
+/// Sizes a game could have been played at, so a bad file can't stall or exhaust the
+/// player while it builds the board.
+fn check_size(
+    line: usize,
+    width: usize,
+    height: usize,
+    mine_count: usize,
+) -> Result<(), ReplayParseError> {
+    let dimensions = 1..=MAX_CUSTOM_DIMENSION;
+    if !dimensions.contains(&width) || !dimensions.contains(&height) {
+        return Err(error(
+            line,
+            &format!(
+                "width and height must be between 1 and {}",
+                MAX_CUSTOM_DIMENSION
+            ),
+        ));
+    }
+    if mine_count > width * height {
+        return Err(error(
+            line,
+            &format!("{} mines don't fit on {} cells", mine_count, width * height),
+        ));
+    }
+    Ok(())
+}
+
+/// A layout the player can place: the header's mine count, every mine on the board and
+/// none listed twice.
+fn check_layout(
+    line: usize,
+    mines: &[(usize, usize)],
+    width: usize,
+    height: usize,
+    mine_count: usize,
+) -> Result<(), ReplayParseError> {
+    if mines.len() != mine_count {
+        return Err(error(
+            line,
+            &format!("expected {} mines, found {}", mine_count, mines.len()),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for &(x, y) in mines {
+        if x >= width || y >= height {
+            return Err(error(
+                line,
+                &format!("mine at ({}, {}) is off the board", x, y),
+            ));
+        }
+        if !seen.insert((x, y)) {
+            return Err(error(
+                line,
+                &format!("mine at ({}, {}) is listed more than once", x, y),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn parse_numbers(line: usize, value: &str) -> Result<Vec<usize>, ReplayParseError> {
+    value
+        .split_whitespace()
+        .map(|n| parse_value(line, n, "number"))
+        .collect()
+}
+
+fn parse_mines(line: usize, value: &str) -> Result<Vec<(usize, usize)>, ReplayParseError> {
+    value
+        .split_whitespace()
+        .map(|pair| {
+            let (x, y) = pair
+                .split_once(',')
+                .ok_or_else(|| error(line, &format!("invalid mine position '{}'", pair)))?;
+            Ok((
+                parse_value(line, x, "mine x")?,
+                parse_value(line, y, "mine y")?,
+            ))
+        })
+        .collect()
+}
+
+fn parse_result(line: usize, value: &str) -> Result<(Option<bool>, u64), ReplayParseError> {
+    let mut parts = value.split_whitespace();
+    let won = match parts.next() {
+        Some("won") => Some(true),
+        Some("lost") => Some(false),
+        Some("unfinished") => return Ok((None, 0)),
+        _ => return Err(error(line, "result must be won, lost or unfinished")),
+    };
+    let duration = parts
+        .next()
+        .ok_or_else(|| error(line, "result is missing its duration"))?;
+    Ok((won, parse_value(line, duration, "duration")?))
+}
+
+fn parse_event(line: usize, text: &str) -> Result<ReplayEvent, ReplayParseError> {
+    let mut parts = text.split_whitespace();
+    let time_ms = parse_value(line, parts.next().unwrap_or(""), "event time")?;
+    let name = parts
+        .next()
+        .ok_or_else(|| error(line, "event is missing its action"))?;
+    let rest: Vec<&str> = parts.collect();
+
+    let cell = |rest: &[&str]| -> Result<(usize, usize), ReplayParseError> {
+        match rest {
+            [x, y] => Ok((parse_value(line, x, "x")?, parse_value(line, y, "y")?)),
+            _ => Err(error(line, &format!("'{}' needs x and y", name))),
+        }
+    };
+
+    let action = match name {
+        "reveal" => {
+            let (x, y) = cell(&rest)?;
+            ReplayAction::Reveal { x, y }
+        }
+        "flag" => {
+            let (x, y) = cell(&rest)?;
+            ReplayAction::Flag { x, y }
+        }
+        "chord" => {
+            let (x, y) = cell(&rest)?;
+            ReplayAction::Chord { x, y }
+        }
+        "undo" => ReplayAction::Undo,
+        "redo" => ReplayAction::Redo,
+        "pause" => ReplayAction::Pause,
+        "resume" => ReplayAction::Resume,
+        _ => return Err(error(line, &format!("unknown action '{}'", name))),
+    };
+    Ok(ReplayEvent { time_ms, action })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoard;
+
+    fn sample() -> Replay {
+        let mut board = GameBoard::with_seed(9, 9, 10, 1234);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+        board.reveal_cell(4, 4);
+        replay.record(0, ReplayAction::Reveal { x: 4, y: 4 });
+        replay.sync_layout(&board);
+        replay.record(1830, ReplayAction::Flag { x: 2, y: 3 });
+        replay.record(2400, ReplayAction::Chord { x: 3, y: 3 });
+        replay.record(5000, ReplayAction::Pause);
+        replay.record(5000, ReplayAction::Resume);
+        replay.record(6100, ReplayAction::Undo);
+        replay.record(6200, ReplayAction::Redo);
+        replay.finish(false, 7000);
+        replay
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let replay = sample();
+        let text = replay.to_string();
+        assert!(text.starts_with(HEADER));
+        assert!(text.contains("result lost 7000"));
+        assert!(text.contains("1830 flag 2 3"));
+        assert_eq!(text.parse::<Replay>().unwrap(), replay);
+    }
+
+    #[test]
+    fn test_round_trip_unfinished_without_layout() {
+        let board = GameBoard::with_seed(16, 16, 40, 3);
+        let replay = Replay::new(&board);
+        assert_eq!(replay.to_string().parse::<Replay>().unwrap(), replay);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_every_layout() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 1234);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+        for (time_ms, action) in [
+            (0, ReplayAction::Reveal { x: 0, y: 0 }),
+            (500, ReplayAction::Undo),
+            (900, ReplayAction::Reveal { x: 8, y: 8 }),
+        ] {
+            action.apply(&mut board);
+            replay.record(time_ms, action);
+            replay.sync_layout(&board);
+        }
+        assert_eq!(replay.layouts.len(), 2);
+
+        let text = replay.to_string();
+        assert!(text.contains("\nlayout 0 "));
+        assert!(text.contains("\nlayout 2 "));
+        assert_eq!(text.parse::<Replay>().unwrap(), replay);
+    }
+
+    #[test]
+    fn test_reads_version_1_mines_line() {
+        let replay = sample();
+        let mines: Vec<String> = replay.layouts[0]
+            .mines
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+        let text = replay
+            .to_string()
+            .replace(HEADER, HEADER_V1)
+            .replace("layout 0 ", "mines ");
+        assert!(text.contains(&format!("mines {}", mines.join(" "))));
+        assert_eq!(text.parse::<Replay>().unwrap(), replay);
+    }
+
+    #[test]
+    fn test_rejects_event_times_going_backwards() {
+        let text = sample().to_string() + "6000 flag 1 1\n";
+        let err = text.parse::<Replay>().unwrap_err();
+        assert_eq!(err.line, text.lines().count());
+        assert!(err.message.contains("backwards"));
+    }
+
+    #[test]
+    fn test_rejects_wrong_header() {
+        let err = "something else\n".parse::<Replay>().unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!("".parse::<Replay>().is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_action() {
+        let text = sample().to_string() + "9000 explode 1 1\n";
+        let err = text.parse::<Replay>().unwrap_err();
+        assert_eq!(err.line, text.lines().count());
+        assert!(err.message.contains("explode"));
+    }
+
+    #[test]
+    fn test_rejects_mine_count_mismatch() {
+        let text = sample().to_string().replace("size 9 9 10", "size 9 9 11");
+        let err = text.parse::<Replay>().unwrap_err();
+        assert!(err.message.contains("expected 11 mines"));
+    }
+
+    #[test]
+    fn test_rejects_impossible_sizes() {
+        for size in ["size 0 9 0", "size 9 1001 10", "size 3 3 10"] {
+            let text = sample().to_string().replace("size 9 9 10", size);
+            let err = text.parse::<Replay>().unwrap_err();
+            assert_eq!(err.line, 2, "{}", size);
+        }
+    }
+
+    #[test]
+    fn test_rejects_layouts_the_board_cannot_hold() {
+        let replay = sample();
+        let layout_line = replay
+            .to_string()
+            .lines()
+            .position(|line| line.starts_with("layout "))
+            .unwrap()
+            + 1;
+        let (x, y) = replay.layouts[0].mines[0];
+        let first = format!("layout 0 {},{} ", x, y);
+        for (bad, message) in [
+            (format!("layout 0 9,{} ", y), "off the board"),
+            (format!("{}{},{} ", first, x, y), "expected 10 mines"),
+        ] {
+            let text = replay.to_string().replace(&first, &bad);
+            let err = text.parse::<Replay>().unwrap_err();
+            assert_eq!(err.line, layout_line);
+            assert!(err.message.contains(message), "{}", err.message);
+        }
+
+        let (x2, y2) = replay.layouts[0].mines[1];
+        let text = replay
+            .to_string()
+            .replace(&format!(" {},{} ", x2, y2), &format!(" {},{} ", x, y));
+        let err = text.parse::<Replay>().unwrap_err();
+        assert!(err.message.contains("more than once"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_rejects_missing_key() {
+        let text: String = sample()
+            .to_string()
+            .lines()
+            .filter(|line| !line.starts_with("seed"))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let err = text.parse::<Replay>().unwrap_err();
+        assert!(err.message.contains("seed"));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod format;
+pub mod player;
+pub mod recording;
+
+pub use format::ReplayParseError;
+pub use player::ReplayPlayer;
+pub use recording::{Replay, ReplayAction, ReplayEvent, ReplayLayout};
@@ -0,0 +1,341 @@
+use super::recording::{Replay, ReplayAction};
+use crate::debug::is_logging_enabled;
+use crate::game::{GameBoard, LayoutError};
+use log::debug;
+use std::time::Duration;
+
+pub const MIN_SPEED: f64 = 0.5;
+pub const MAX_SPEED: f64 = 8.0;
+
+/// Drives a [`GameBoard`] through a recorded game. `position` is the number of events
+/// applied so far and `time_ms` the simulated game clock.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    board: GameBoard,
+    position: usize,
+    time_ms: f64,
+    playing: bool,
+    speed: f64,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Result<Self, LayoutError> {
+        if is_logging_enabled() {
+            debug!(
+                "Loading replay: {}x{}, {} mines, {} events",
+                replay.width,
+                replay.height,
+                replay.mine_count,
+                replay.events.len()
+            );
+        }
+        let board = replay.initial_board()?;
+        Ok(Self {
+            replay,
+            board,
+            position: 0,
+            time_ms: 0.0,
+            playing: false,
+            speed: 1.0,
+        })
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn board(&self) -> &GameBoard {
+        &self.board
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn len(&self) -> usize {
+        self.replay.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replay.events.is_empty()
+    }
+
+    pub fn time(&self) -> Duration {
+        Duration::from_millis(self.time_ms as u64)
+    }
+
+    /// Length of the recording on the game clock.
+    pub fn duration_ms(&self) -> u64 {
+        let last_event = self.replay.events.last().map_or(0, |e| e.time_ms);
+        last_event.max(self.replay.duration_ms)
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time_ms as u64 >= self.duration_ms() && self.position == self.len()
+    }
+
+    /// Starts playback, rewinding first if the replay already reached its end.
+    pub fn play(&mut self) {
+        if self.is_finished() {
+            self.seek_time(0);
+        }
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn toggle_playing(&mut self) {
+        if self.playing {
+            self.pause();
+        } else {
+            self.play();
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Sets the playback rate, clamped to `MIN_SPEED..=MAX_SPEED`.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    /// Cell targeted by the most recently applied click, if any.
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        self.replay.events[..self.position]
+            .iter()
+            .rev()
+            .find_map(|event| match event.action {
+                ReplayAction::Reveal { x, y }
+                | ReplayAction::Flag { x, y }
+                | ReplayAction::Chord { x, y } => Some((x, y)),
+                _ => None,
+            })
+    }
+
+    /// Advances the simulated clock by `elapsed` real time, scaled by the speed, and
+    /// applies every event that has come due. Does nothing while paused.
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.playing {
+            return;
+        }
+        self.time_ms += elapsed.as_secs_f64() * 1000.0 * self.speed;
+        self.apply_due_events();
+        if self.is_finished() {
+            self.time_ms = self.duration_ms() as f64;
+            self.playing = false;
+        }
+    }
+
+    /// Applies exactly one event, even if the next ones share its timestamp.
+    pub fn step_forward(&mut self) {
+        if let Some(event) = self.replay.events.get(self.position) {
+            self.time_ms = self.time_ms.max(event.time_ms as f64);
+            self.replay.apply_event(&mut self.board, self.position);
+            self.position += 1;
+        }
+    }
+
+    pub fn step_back(&mut self) {
+        if self.position == 0 {
+            return;
+        }
+        let target = self.position - 1;
+        self.rebuild(target);
+        self.time_ms = match target {
+            0 => 0.0,
+            n => self.replay.events[n - 1].time_ms as f64,
+        };
+    }
+
+    /// Jumps the clock to `time_ms`, replaying from the start when going backwards.
+    pub fn seek_time(&mut self, time_ms: u64) {
+        let time_ms = time_ms.min(self.duration_ms());
+        let target = self
+            .replay
+            .events
+            .partition_point(|event| event.time_ms <= time_ms);
+        if target < self.position {
+            self.rebuild(target);
+        }
+        self.time_ms = time_ms as f64;
+        self.apply_due_events();
+    }
+
+    fn apply_due_events(&mut self) {
+        while let Some(event) = self.replay.events.get(self.position) {
+            if event.time_ms as f64 > self.time_ms {
+                break;
+            }
+            self.replay.apply_event(&mut self.board, self.position);
+            self.position += 1;
+        }
+    }
+
+    fn rebuild(&mut self, position: usize) {
+        // The layouts were validated when the player was created
+        self.board = self
+            .replay
+            .initial_board()
+            .expect("replay layouts already validated");
+        for index in 0..position {
+            self.replay.apply_event(&mut self.board, index);
+        }
+        self.position = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_game() -> (Replay, GameBoard) {
+        let mut board = GameBoard::with_seed(9, 9, 10, 11);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+        let mut play = |board: &mut GameBoard, time_ms: u64, action: ReplayAction| {
+            action.apply(board);
+            replay.record(time_ms, action);
+            replay.sync_layout(board);
+        };
+
+        play(&mut board, 0, ReplayAction::Reveal { x: 4, y: 4 });
+        let (mx, my) = board.mine_positions()[0];
+        play(&mut board, 1000, ReplayAction::Flag { x: mx, y: my });
+        play(&mut board, 2000, ReplayAction::Pause);
+        play(&mut board, 2000, ReplayAction::Resume);
+        play(&mut board, 3000, ReplayAction::Flag { x: mx, y: my });
+        replay.finish(false, 3500);
+        (replay, board)
+    }
+
+    #[test]
+    fn test_new_player_starts_at_beginning() {
+        let (replay, _) = recorded_game();
+        let player = ReplayPlayer::new(replay).unwrap();
+
+        assert_eq!(player.position(), 0);
+        assert_eq!(player.len(), 5);
+        assert!(!player.is_playing());
+        assert!(player
+            .board()
+            .board
+            .iter()
+            .flatten()
+            .all(|cell| cell.state == crate::CellState::Hidden));
+        assert_eq!(player.duration_ms(), 3500);
+    }
+
+    #[test]
+    fn test_advance_applies_due_events_scaled_by_speed() {
+        let (replay, _) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.advance(Duration::from_millis(500));
+        assert_eq!(player.position(), 0);
+
+        player.play();
+        player.advance(Duration::from_millis(0));
+        assert_eq!(player.position(), 1);
+
+        player.set_speed(2.0);
+        player.advance(Duration::from_millis(500));
+        assert_eq!(player.position(), 2);
+        assert_eq!(player.time(), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_playing_to_the_end_matches_recorded_board() {
+        let (replay, board) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.play();
+        player.advance(Duration::from_secs(10));
+
+        assert!(player.is_finished());
+        assert!(!player.is_playing());
+        assert_eq!(player.time(), Duration::from_millis(3500));
+        assert_eq!(player.board().board, board.board);
+    }
+
+    #[test]
+    fn test_step_back_and_forward() {
+        let (replay, _) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.step_forward();
+        player.step_forward();
+        assert_eq!(player.position(), 2);
+        assert_eq!(player.board().flags_placed(), 1);
+
+        player.step_back();
+        assert_eq!(player.position(), 1);
+        assert_eq!(player.board().flags_placed(), 0);
+        assert_eq!(player.time(), Duration::ZERO);
+
+        player.step_back();
+        player.step_back();
+        assert_eq!(player.position(), 0);
+    }
+
+    #[test]
+    fn test_seek_time_backwards_rebuilds_board() {
+        let (replay, _) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.seek_time(2500);
+        assert_eq!(player.position(), 4);
+        player.seek_time(1000);
+        assert_eq!(player.position(), 2);
+        assert_eq!(player.board().flags_placed(), 1);
+        player.seek_time(u64::MAX);
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn test_cursor_follows_last_click() {
+        let (replay, _) = recorded_game();
+        let first_flag = match replay.events[1].action {
+            ReplayAction::Flag { x, y } => (x, y),
+            _ => unreachable!(),
+        };
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        assert_eq!(player.cursor(), None);
+        player.step_forward();
+        assert_eq!(player.cursor(), Some((4, 4)));
+        player.seek_time(2000);
+        assert_eq!(player.cursor(), Some(first_flag));
+    }
+
+    #[test]
+    fn test_speed_is_clamped() {
+        let (replay, _) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.set_speed(100.0);
+        assert_eq!(player.speed(), MAX_SPEED);
+        player.set_speed(0.1);
+        assert_eq!(player.speed(), MIN_SPEED);
+    }
+
+    #[test]
+    fn test_play_after_finish_restarts() {
+        let (replay, _) = recorded_game();
+        let mut player = ReplayPlayer::new(replay).unwrap();
+
+        player.seek_time(u64::MAX);
+        player.play();
+        assert!(player.is_playing());
+        assert_eq!(player.position(), 1);
+        assert_eq!(player.time(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::game::{GameBoard, LayoutError, PlacementPolicy};
+
+/// One input the board received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayAction {
+    Reveal { x: usize, y: usize },
+    Flag { x: usize, y: usize },
+    Chord { x: usize, y: usize },
+    Undo,
+    Redo,
+    Pause,
+    Resume,
+}
+
+impl ReplayAction {
+    /// Applies the action to `board`. Pause and resume only affect the timer, so they
+    /// leave the board untouched.
+    pub fn apply(&self, board: &mut GameBoard) {
+        match *self {
+            ReplayAction::Reveal { x, y } => {
+                board.reveal_cell(x, y);
+            }
+            ReplayAction::Flag { x, y } => {
+                board.toggle_flag(x, y);
+            }
+            ReplayAction::Chord { x, y } => {
+                board.chord(x, y);
+            }
+            ReplayAction::Undo => {
+                board.undo();
+            }
+            ReplayAction::Redo => {
+                board.redo();
+            }
+            ReplayAction::Pause | ReplayAction::Resume => {}
+        }
+    }
+}
+
+/// An action stamped with the game clock, in milliseconds since the timer started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayEvent {
+    pub time_ms: u64,
+    pub action: ReplayAction,
+}
+
+/// A mine layout and the event it was placed by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayLayout {
+    /// Index of the first event played on this layout.
+    pub from_event: usize,
+    pub mines: Vec<(usize, usize)>,
+}
+
+/// Everything needed to reconstruct a game: the board setup and every timed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub width: usize,
+    pub height: usize,
+    pub mine_count: usize,
+    pub seed: u64,
+    pub placement: PlacementPolicy,
+    pub no_guess: bool,
+    pub question_marks: bool,
+    /// Every layout the game was played on, in event order. Empty until the first reveal
+    /// places the mines; undoing that reveal and clicking elsewhere adds another.
+    pub layouts: Vec<ReplayLayout>,
+    pub events: Vec<ReplayEvent>,
+    /// `Some(true)` for a win, `Some(false)` for a loss, `None` while still in progress.
+    pub won: Option<bool>,
+    /// Clock time when the game ended.
+    pub duration_ms: u64,
+}
+
+impl Replay {
+    /// Starts an empty recording for `board`, taking the layout if mines are already placed.
+    pub fn new(board: &GameBoard) -> Self {
+        let mut replay = Self {
+            width: board.width,
+            height: board.height,
+            mine_count: board.mine_count,
+            seed: board.seed,
+            placement: board.placement,
+            no_guess: board.no_guess,
+            question_marks: board.question_marks,
+            layouts: Vec::new(),
+            events: Vec::new(),
+            won: None,
+            duration_ms: 0,
+        };
+        replay.sync_layout(board);
+        replay
+    }
+
+    pub fn record(&mut self, time_ms: u64, action: ReplayAction) {
+        self.events.push(ReplayEvent { time_ms, action });
+    }
+
+    /// Picks up the mine layout once the board has one, starting from the last recorded
+    /// event. Undoing the first reveal can lead to a different layout, which is kept
+    /// alongside the earlier ones.
+    pub fn sync_layout(&mut self, board: &GameBoard) {
+        if board.first_click {
+            return;
+        }
+        let mines = board.mine_positions();
+        if self.layouts.last().map(|layout| &layout.mines) != Some(&mines) {
+            self.layouts.push(ReplayLayout {
+                from_event: self.events.len().saturating_sub(1),
+                mines,
+            });
+        }
+    }
+
+    pub fn finish(&mut self, won: bool, duration_ms: u64) {
+        self.won = Some(won);
+        self.duration_ms = duration_ms;
+    }
+
+    /// A started board in the state the recording began from, ready for the events to
+    /// be applied in order with [`Replay::apply_event`]. Mines come from the seed until
+    /// an event brings in a recorded layout. Every layout is checked here, so applying
+    /// the events can't fail afterwards.
+    pub fn initial_board(&self) -> Result<GameBoard, LayoutError> {
+        let mut board = GameBoard::with_seed(self.width, self.height, self.mine_count, self.seed);
+        for layout in &self.layouts {
+            board.set_layout(&layout.mines)?;
+        }
+        board.reset();
+        board.placement = self.placement;
+        board.no_guess = self.no_guess;
+        board.question_marks = self.question_marks;
+        board.game_started = true;
+        Ok(board)
+    }
+
+    /// Applies event `index` to `board`, first placing the layout that starts there.
+    pub fn apply_event(&self, board: &mut GameBoard, index: usize) {
+        if let Some(layout) = self.layouts.iter().find(|l| l.from_event == index) {
+            board
+                .set_layout(&layout.mines)
+                .expect("replay layouts are checked by initial_board");
+        }
+        self.events[index].action.apply(board);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(board: &mut GameBoard, replay: &mut Replay, time_ms: u64, action: ReplayAction) {
+        action.apply(board);
+        replay.record(time_ms, action);
+        replay.sync_layout(board);
+    }
+
+    #[test]
+    fn test_new_replay_copies_board_setup() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 42);
+        board.placement = PlacementPolicy::SafeCell;
+        board.question_marks = true;
+        let replay = Replay::new(&board);
+
+        assert_eq!((replay.width, replay.height, replay.mine_count), (9, 9, 10));
+        assert_eq!(replay.seed, 42);
+        assert_eq!(replay.placement, PlacementPolicy::SafeCell);
+        assert!(replay.question_marks);
+        assert!(replay.layouts.is_empty());
+        assert!(replay.events.is_empty());
+        assert_eq!(replay.won, None);
+    }
+
+    #[test]
+    fn test_layout_recorded_after_first_reveal() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 7);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+
+        play(
+            &mut board,
+            &mut replay,
+            0,
+            ReplayAction::Reveal { x: 4, y: 4 },
+        );
+
+        assert_eq!(replay.layouts.len(), 1);
+        assert_eq!(replay.layouts[0].from_event, 0);
+        assert_eq!(replay.layouts[0].mines, board.mine_positions());
+        assert_eq!(replay.layouts[0].mines.len(), 10);
+    }
+
+    #[test]
+    fn test_replaying_events_reproduces_board() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 99);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+
+        play(
+            &mut board,
+            &mut replay,
+            0,
+            ReplayAction::Reveal { x: 0, y: 0 },
+        );
+        let (mx, my) = board.mine_positions()[0];
+        play(
+            &mut board,
+            &mut replay,
+            400,
+            ReplayAction::Flag { x: mx, y: my },
+        );
+        play(&mut board, &mut replay, 900, ReplayAction::Pause);
+        play(&mut board, &mut replay, 900, ReplayAction::Resume);
+        play(
+            &mut board,
+            &mut replay,
+            1200,
+            ReplayAction::Reveal { x: 8, y: 8 },
+        );
+
+        let mut replayed = replay.initial_board().unwrap();
+        for index in 0..replay.events.len() {
+            replay.apply_event(&mut replayed, index);
+        }
+
+        assert_eq!(replayed.board, board.board);
+        assert_eq!(replayed.game_over, board.game_over);
+        assert_eq!(replayed.flags_placed(), board.flags_placed());
+    }
+
+    #[test]
+    fn test_each_layout_is_replayed_from_its_own_event() {
+        let mut board = GameBoard::with_seed(9, 9, 10, 99);
+        board.start_game();
+        let mut replay = Replay::new(&board);
+
+        play(
+            &mut board,
+            &mut replay,
+            0,
+            ReplayAction::Reveal { x: 0, y: 0 },
+        );
+        let first_board = board.board.clone();
+        play(&mut board, &mut replay, 300, ReplayAction::Undo);
+        play(
+            &mut board,
+            &mut replay,
+            600,
+            ReplayAction::Reveal { x: 8, y: 8 },
+        );
+
+        assert_eq!(replay.layouts.len(), 2);
+        assert_eq!(replay.layouts[1].from_event, 2);
+        assert_ne!(replay.layouts[0].mines, replay.layouts[1].mines);
+
+        let mut replayed = replay.initial_board().unwrap();
+        replay.apply_event(&mut replayed, 0);
+        assert_eq!(replayed.board, first_board);
+        replay.apply_event(&mut replayed, 1);
+        replay.apply_event(&mut replayed, 2);
+        assert_eq!(replayed.board, board.board);
+        assert_eq!(replayed.game_over, board.game_over);
+    }
+
+    #[test]
+    fn test_initial_board_without_layout_uses_seed() {
+        let board = GameBoard::with_seed(9, 9, 10, 5);
+        let replay = Replay::new(&board);
+
+        let mut rebuilt = replay.initial_board().unwrap();
+        assert!(rebuilt.game_started);
+        assert!(rebuilt.first_click);
+
+        let mut original = GameBoard::with_seed(9, 9, 10, 5);
+        original.start_game();
+        original.reveal_cell(3, 3);
+        rebuilt.reveal_cell(3, 3);
+        assert_eq!(rebuilt.mine_positions(), original.mine_positions());
+    }
+
+    #[test]
+    fn test_finish_sets_result() {
+        let board = GameBoard::with_seed(9, 9, 10, 1);
+        let mut replay = Replay::new(&board);
+        replay.finish(true, 12_345);
+        assert_eq!(replay.won, Some(true));
+        assert_eq!(replay.duration_ms, 12_345);
+    }
+}
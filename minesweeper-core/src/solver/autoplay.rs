@@ -0,0 +1,80 @@
+use super::deduction::deduce;
+use super::hint::{hint, Hint};
+use super::view::BoardView;
+use crate::game::GameBoard;
+
+/// How a game played by [`autoplay`] went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoplayOutcome {
+    pub won: bool,
+    /// Reveals made without proof that the cell was safe, not counting the first click.
+    pub guesses: usize,
+    pub three_bv: Option<usize>,
+}
+
+/// Plays `board` to the end from `first_click`: every cell the solver proves safe is
+/// revealed, and when there is none, the cell least likely to be a mine. Ties go to the
+/// first cell in row order, so a board is always played the same way.
+pub fn autoplay(board: &mut GameBoard, first_click: (usize, usize)) -> AutoplayOutcome {
+    if !board.game_started {
+        board.start_game();
+    }
+    board.reveal_cell(first_click.0, first_click.1);
+
+    let mut guesses = 0;
+    while !board.game_over {
+        let view = BoardView::from_board(board);
+        let deductions = deduce(&view);
+        if !deductions.safe.is_empty() {
+            for (x, y) in deductions.safe {
+                board.reveal_cell(x, y);
+            }
+            continue;
+        }
+        match hint(&view) {
+            Some(Hint::Safe { x, y, .. }) => {
+                board.reveal_cell(x, y);
+            }
+            Some(Hint::Guess { x, y, .. }) => {
+                guesses += 1;
+                board.reveal_cell(x, y);
+            }
+            None => break,
+        }
+    }
+
+    AutoplayOutcome {
+        won: board.game_won,
+        guesses,
+        three_bv: board.three_bv(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlacementPolicy;
+
+    #[test]
+    fn test_logic_only_board_needs_no_guesses() {
+        let mut board = GameBoard::from_layout(5, 5, &[(0, 0), (4, 4)]).unwrap();
+        let outcome = autoplay(&mut board, (2, 2));
+
+        assert!(outcome.won);
+        assert_eq!(outcome.guesses, 0);
+        assert_eq!(outcome.three_bv, board.three_bv());
+    }
+
+    #[test]
+    fn test_same_seed_plays_the_same_game() {
+        let play = |seed| {
+            let mut board = GameBoard::with_seed(30, 16, 99, seed);
+            board.placement = PlacementPolicy::SafeOpening;
+            autoplay(&mut board, (15, 8))
+        };
+
+        for seed in 0..5 {
+            assert_eq!(play(seed), play(seed));
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use super::view::{BoardView, VisibleCell};
+
+/// Hidden cells whose contents follow from the visible state. Positions are `(x, y)`,
+/// in row order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deductions {
+    pub safe: Vec<(usize, usize)>,
+    pub mines: Vec<(usize, usize)>,
+}
+
+impl Deductions {
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.mines.is_empty()
+    }
+}
+
+/// Exactly `mines` of `cells` are mines. Built from the revealed number at `center`,
+/// minus the neighbours already known.
+#[derive(Debug, Clone)]
+pub(crate) struct Constraint {
+    pub(crate) center: usize,
+    pub(crate) cells: Vec<usize>,
+    pub(crate) mines: usize,
+}
+
+/// Which rule decided a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rule {
+    /// The number at this index had all of its mines, or all of its cells were mines.
+    Number(usize),
+    /// Comparing the first number with the second decided the first one's other cells.
+    Pair(usize, usize),
+    MineCount,
+}
+
+/// A cell decided by `rule`, in the order the rules found them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Finding {
+    pub(crate) index: usize,
+    pub(crate) mine: bool,
+    pub(crate) rule: Rule,
+}
+
+/// Finds every hidden cell that is certainly safe or certainly a mine. Flags are trusted
+/// as mines. Deductions are applied as they are found, so later rounds build on earlier
+/// ones, until nothing more follows from:
+///
+/// - single numbers: all remaining neighbours are safe, or all are mines;
+/// - pairs of overlapping numbers, which bound how many mines their shared cells hold
+///   (this covers subsets, 1-2-1 and 1-2-2-1);
+/// - the total mine count, once every mine or every safe cell is accounted for.
+pub fn deduce(view: &BoardView) -> Deductions {
+    let mut known = initial_knowledge(view);
+    let mut found = propagate(view, &mut known);
+
+    found.sort_unstable_by_key(|finding| finding.index);
+    let mut deductions = Deductions::default();
+    for finding in found {
+        let position = view.position(finding.index);
+        if finding.mine {
+            deductions.mines.push(position);
+        } else {
+            deductions.safe.push(position);
+        }
+    }
+    deductions
+}
+
+/// `Some(true)` for flags, `Some(false)` for revealed cells and `None` for hidden ones.
+pub(crate) fn initial_knowledge(view: &BoardView) -> Vec<Option<bool>> {
+    (0..view.len())
+        .map(|index| match view.cell_at(index) {
+            VisibleCell::Hidden => None,
+            VisibleCell::Flagged => Some(true),
+            VisibleCell::Revealed(_) => Some(false),
+        })
+        .collect()
+}
+
+/// Runs the rules until nothing more follows, filling in `known` and returning what was
+/// decided, simplest rules first.
+pub(crate) fn propagate(view: &BoardView, known: &mut [Option<bool>]) -> Vec<Finding> {
+    let mut found = Vec::new();
+    loop {
+        let constraints = constraints(view, known);
+        let mut round = single_cell(&constraints);
+        if round.is_empty() {
+            round = pairs(view, &constraints);
+        }
+        if round.is_empty() {
+            round = mine_count(view, known);
+        }
+        if round.is_empty() {
+            return found;
+        }
+        for finding in round {
+            if known[finding.index].is_none() {
+                known[finding.index] = Some(finding.mine);
+                found.push(finding);
+            }
+        }
+    }
+}
+
+/// One constraint per revealed number that still borders an undecided cell. Numbers
+/// contradicted by the flags around them are skipped.
+pub(crate) fn constraints(view: &BoardView, known: &[Option<bool>]) -> Vec<Constraint> {
+    let mut constraints = Vec::new();
+    for index in 0..view.len() {
+        let VisibleCell::Revealed(number) = view.cell_at(index) else {
+            continue;
+        };
+        let mut cells = Vec::new();
+        let mut mines = 0;
+        for neighbor in view.neighbors(index) {
+            match known[neighbor] {
+                None => cells.push(neighbor),
+                Some(true) => mines += 1,
+                Some(false) => {}
+            }
+        }
+        let Some(remaining) = (number as usize).checked_sub(mines) else {
+            continue;
+        };
+        if !cells.is_empty() && remaining <= cells.len() {
+            constraints.push(Constraint {
+                center: index,
+                cells,
+                mines: remaining,
+            });
+        }
+    }
+    constraints
+}
+
+fn single_cell(constraints: &[Constraint]) -> Vec<Finding> {
+    let mut found = Vec::new();
+    for constraint in constraints {
+        let mine = match constraint.mines {
+            0 => false,
+            n if n == constraint.cells.len() => true,
+            _ => continue,
+        };
+        found.extend(constraint.cells.iter().map(|&index| Finding {
+            index,
+            mine,
+            rule: Rule::Number(constraint.center),
+        }));
+    }
+    found
+}
+
+/// For two constraints sharing cells, the shared part holds between `low` and `high`
+/// mines. If that forces the cells only one side sees to be all mines or all safe,
+/// they are decided.
+fn pairs(view: &BoardView, constraints: &[Constraint]) -> Vec<Finding> {
+    let mut by_cell: Vec<Vec<usize>> = vec![Vec::new(); view.len()];
+    for (id, constraint) in constraints.iter().enumerate() {
+        for &cell in &constraint.cells {
+            by_cell[cell].push(id);
+        }
+    }
+
+    let mut found = Vec::new();
+    for (a_id, a) in constraints.iter().enumerate() {
+        let mut partners: Vec<usize> = a
+            .cells
+            .iter()
+            .flat_map(|&cell| by_cell[cell].iter().copied())
+            .filter(|&b_id| b_id > a_id)
+            .collect();
+        partners.sort_unstable();
+        partners.dedup();
+
+        for b in partners.into_iter().map(|b_id| &constraints[b_id]) {
+            let shared = a.cells.iter().filter(|cell| b.cells.contains(cell)).count();
+            let low = a
+                .mines
+                .saturating_sub(a.cells.len() - shared)
+                .max(b.mines.saturating_sub(b.cells.len() - shared));
+            let high = shared.min(a.mines).min(b.mines);
+            if low > high {
+                continue;
+            }
+            for (this, other) in [(a, b), (b, a)] {
+                let only: Vec<usize> = this
+                    .cells
+                    .iter()
+                    .copied()
+                    .filter(|cell| !other.cells.contains(cell))
+                    .collect();
+                if only.is_empty() {
+                    continue;
+                }
+                let mine = if this.mines == low {
+                    false
+                } else if this.mines - high == only.len() {
+                    true
+                } else {
+                    continue;
+                };
+                found.extend(only.into_iter().map(|index| Finding {
+                    index,
+                    mine,
+                    rule: Rule::Pair(this.center, other.center),
+                }));
+            }
+        }
+    }
+    found
+}
+
+fn mine_count(view: &BoardView, known: &[Option<bool>]) -> Vec<Finding> {
+    let undecided: Vec<usize> = (0..view.len()).filter(|&i| known[i].is_none()).collect();
+    let mines_known = known.iter().filter(|k| **k == Some(true)).count();
+    let mines_left = view.mine_count.saturating_sub(mines_known);
+    let mine = if mines_left == 0 {
+        false
+    } else if mines_left == undecided.len() {
+        true
+    } else {
+        return Vec::new();
+    };
+    undecided
+        .into_iter()
+        .map(|index| Finding {
+            index,
+            mine,
+            rule: Rule::MineCount,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoard;
+
+    fn deduce_ascii(text: &str) -> Deductions {
+        deduce(&BoardView::from_board(
+            &GameBoard::from_ascii(text).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn test_single_cell_rules() {
+        // The 1 at (0, 1) sees only one hidden cell, so it is a mine; the 1 at (1, 0)
+        // is then satisfied.
+        let deductions = deduce_ascii(
+            "*1..
+             11..
+             00..",
+        );
+
+        assert!(deductions.mines.contains(&(0, 0)));
+        assert!(deductions.safe.contains(&(2, 0)));
+        assert!(deductions.safe.contains(&(2, 1)));
+    }
+
+    #[test]
+    fn test_one_two_one() {
+        let deductions = deduce_ascii(
+            "..*.*..
+             ..121..",
+        );
+
+        assert!(deductions.mines.contains(&(2, 0)));
+        assert!(deductions.mines.contains(&(4, 0)));
+        for safe in [(1, 0), (3, 0), (5, 0), (1, 1), (5, 1)] {
+            assert!(deductions.safe.contains(&safe), "{:?} not safe", safe);
+        }
+    }
+
+    #[test]
+    fn test_one_two_two_one() {
+        let deductions = deduce_ascii(
+            "...**...
+             ..1221..",
+        );
+
+        assert!(deductions.mines.contains(&(3, 0)));
+        assert!(deductions.mines.contains(&(4, 0)));
+        for safe in [(2, 0), (5, 0), (1, 0), (6, 0)] {
+            assert!(deductions.safe.contains(&safe), "{:?} not safe", safe);
+        }
+    }
+
+    #[test]
+    fn test_one_one_against_wall() {
+        // Both 1s share the mine between (0, 0) and (1, 0), so the cells only the second
+        // one sees are safe. Which of the two holds the mine is a guess.
+        let deductions = deduce_ascii(
+            ".*..
+             11..",
+        );
+
+        assert_eq!(deductions.safe, vec![(2, 0), (2, 1)]);
+        assert!(deductions.mines.is_empty());
+    }
+
+    #[test]
+    fn test_fifty_fifty_has_no_deductions() {
+        assert!(deduce_ascii("*.\n11").is_empty());
+        assert!(deduce_ascii(".*\n11").is_empty());
+    }
+
+    #[test]
+    fn test_flags_count_as_mines() {
+        let deductions = deduce_ascii(
+            "F*.
+             221
+             000",
+        );
+
+        assert_eq!(deductions.mines, vec![(1, 0)]);
+        assert_eq!(deductions.safe, vec![(2, 0)]);
+    }
+
+    #[test]
+    fn test_total_mine_count_decides_remaining_cells() {
+        // The only mine is flagged, so the cells no number sees are safe too.
+        let deductions = deduce_ascii(
+            "F1...
+             11...
+             .....",
+        );
+        assert!(deductions.mines.is_empty());
+        assert_eq!(deductions.safe.len(), 11);
+        assert!(deductions.safe.contains(&(4, 2)));
+
+        assert_eq!(deduce(&BoardView::new(3, 1, 3)).mines.len(), 3);
+        assert_eq!(deduce(&BoardView::new(3, 1, 0)).safe.len(), 3);
+        assert!(deduce(&BoardView::new(3, 1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_deductions_match_hidden_layout() {
+        let mut board = GameBoard::with_seed(30, 16, 99, 8);
+        board.start_game();
+        board.reveal_cell(15, 8);
+        let deductions = deduce(&BoardView::from_board(&board));
+
+        assert!(!deductions.is_empty());
+        for &(x, y) in &deductions.mines {
+            assert!(board.board[y][x].content == crate::CellContent::Mine);
+        }
+        for &(x, y) in &deductions.safe {
+            assert!(board.board[y][x].content != crate::CellContent::Mine);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use super::deduction::{initial_knowledge, propagate, Rule};
+use super::probability::mine_probabilities;
+use super::view::{BoardView, VisibleCell};
+use std::fmt;
+
+/// Why a hinted cell is safe.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    /// The number at `number` already touches `value` mines. `unflagged` lists those
+    /// that are certain but not flagged yet.
+    Satisfied {
+        number: (usize, usize),
+        value: u8,
+        unflagged: Vec<(usize, usize)>,
+    },
+    /// Comparing the number at `number` with the overlapping one at `other` leaves no
+    /// room for a mine here.
+    Overlap {
+        number: (usize, usize),
+        value: u8,
+        other: (usize, usize),
+        other_value: u8,
+    },
+    /// Every mine on the board is already accounted for.
+    AllMinesFound,
+    /// No layout that fits the numbers puts a mine here, though no single rule shows it.
+    NoLayoutFits,
+}
+
+/// A suggested next move.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hint {
+    /// A cell that is certainly safe.
+    Safe { x: usize, y: usize, reason: Reason },
+    /// No cell is certainly safe; this one has the lowest chance of a mine.
+    Guess {
+        x: usize,
+        y: usize,
+        probability: f64,
+    },
+}
+
+impl Hint {
+    pub fn position(&self) -> (usize, usize) {
+        match *self {
+            Hint::Safe { x, y, .. } | Hint::Guess { x, y, .. } => (x, y),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, reason) = match self {
+            Hint::Guess { x, y, probability } => {
+                return write!(
+                    f,
+                    "Nothing is certain here. ({}, {}) is the safest guess, with a {:.0}% chance of a mine.",
+                    x,
+                    y,
+                    probability * 100.0
+                );
+            }
+            Hint::Safe { x, y, reason } => (x, y, reason),
+        };
+        write!(f, "({}, {}) is safe: ", x, y)?;
+        match reason {
+            Reason::Satisfied {
+                number: (nx, ny),
+                value,
+                unflagged,
+            } => {
+                let mines = if *value == 1 { "mine" } else { "mines" };
+                if unflagged.is_empty() {
+                    write!(
+                        f,
+                        "the {} at ({}, {}) already touches {} flagged {}.",
+                        value, nx, ny, value, mines
+                    )
+                } else {
+                    let cells: Vec<String> = unflagged
+                        .iter()
+                        .map(|(mx, my)| format!("({}, {})", mx, my))
+                        .collect();
+                    write!(
+                        f,
+                        "the {} at ({}, {}) already touches {} {}, counting {}.",
+                        value,
+                        nx,
+                        ny,
+                        value,
+                        mines,
+                        cells.join(" and ")
+                    )
+                }
+            }
+            Reason::Overlap {
+                number: (nx, ny),
+                value,
+                other: (ox, oy),
+                other_value,
+            } => write!(
+                f,
+                "comparing the {} at ({}, {}) with the {} at ({}, {}) leaves no room for a mine.",
+                value, nx, ny, other_value, ox, oy
+            ),
+            Reason::AllMinesFound => write!(f, "every mine is already accounted for."),
+            Reason::NoLayoutFits => write!(
+                f,
+                "no arrangement of mines that fits the numbers puts one here."
+            ),
+        }
+    }
+}
+
+/// The simplest provably safe cell, or the lowest-risk guess when there is none.
+/// `None` when no hidden cell is left or no layout fits the board. Flags may be wrong,
+/// so the reasoning ignores them; a flagged cell is only suggested when the flag is
+/// wrong and nothing unflagged is certainly safe.
+pub fn hint(player_view: &BoardView) -> Option<Hint> {
+    let view = &player_view.without_flags();
+    let flagged = |index: usize| player_view.cell_at(index) == VisibleCell::Flagged;
+    let mut known = initial_knowledge(view);
+    let found = propagate(view, &mut known);
+
+    let safe = found
+        .iter()
+        .find(|finding| !finding.mine && !flagged(finding.index))
+        .or_else(|| found.iter().find(|finding| !finding.mine));
+    if let Some(safe) = safe {
+        let (x, y) = view.position(safe.index);
+        let value = |index: usize| match view.cell_at(index) {
+            VisibleCell::Revealed(n) => n,
+            _ => 0,
+        };
+        let reason = match safe.rule {
+            Rule::Number(number) => Reason::Satisfied {
+                number: view.position(number),
+                value: value(number),
+                unflagged: found
+                    .iter()
+                    .take_while(|finding| finding.index != safe.index)
+                    .filter(|finding| {
+                        finding.mine
+                            && !flagged(finding.index)
+                            && view.neighbors(number).any(|n| n == finding.index)
+                    })
+                    .map(|finding| view.position(finding.index))
+                    .collect(),
+            },
+            Rule::Pair(number, other) => Reason::Overlap {
+                number: view.position(number),
+                value: value(number),
+                other: view.position(other),
+                other_value: value(other),
+            },
+            Rule::MineCount => Reason::AllMinesFound,
+        };
+        return Some(Hint::Safe { x, y, reason });
+    }
+
+    let probabilities = mine_probabilities(view)?;
+    let mut best: Option<(usize, usize, f64)> = None;
+    for y in 0..view.height {
+        for x in 0..view.width {
+            if player_view.get(x, y) != VisibleCell::Hidden {
+                continue;
+            }
+            let Some(probability) = probabilities.get(x, y) else {
+                continue;
+            };
+            if !matches!(best, Some((_, _, lowest)) if lowest <= probability) {
+                best = Some((x, y, probability));
+            }
+        }
+    }
+    let (x, y, probability) = best?;
+    if probability < 1e-9 {
+        return Some(Hint::Safe {
+            x,
+            y,
+            reason: Reason::NoLayoutFits,
+        });
+    }
+    Some(Hint::Guess { x, y, probability })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoard;
+
+    fn hint_ascii(text: &str) -> Option<Hint> {
+        hint(&BoardView::from_board(
+            &GameBoard::from_ascii(text).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn test_satisfied_number_with_flag() {
+        let hint = hint_ascii(
+            "F1.
+             11.",
+        )
+        .unwrap();
+
+        assert_eq!(hint.position(), (2, 0));
+        assert_eq!(
+            hint,
+            Hint::Safe {
+                x: 2,
+                y: 0,
+                reason: Reason::Satisfied {
+                    number: (1, 0),
+                    value: 1,
+                    unflagged: vec![],
+                },
+            }
+        );
+        assert_eq!(
+            hint.to_string(),
+            "(2, 0) is safe: the 1 at (1, 0) already touches 1 flagged mine."
+        );
+    }
+
+    #[test]
+    fn test_satisfied_number_names_unflagged_mines() {
+        let hint = hint_ascii(
+            "*1.
+             11.",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hint.to_string(),
+            "(2, 0) is safe: the 1 at (1, 0) already touches 1 mine, counting (0, 0)."
+        );
+    }
+
+    #[test]
+    fn test_overlap_explanation() {
+        let hint = hint_ascii(
+            ".*..
+             11..",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hint,
+            Hint::Safe {
+                x: 2,
+                y: 0,
+                reason: Reason::Overlap {
+                    number: (1, 1),
+                    value: 1,
+                    other: (0, 1),
+                    other_value: 1,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_guess_when_nothing_is_safe() {
+        // A 50/50 on the left; the other mine is in one of the three cells on the right.
+        let mut view = BoardView::new(6, 1, 2);
+        view.set(1, 0, VisibleCell::Revealed(1));
+        let hint = hint(&view).unwrap();
+
+        assert!(matches!(hint, Hint::Guess { x: 3, y: 0, .. }));
+        assert!(hint.to_string().starts_with("Nothing is certain here."));
+    }
+
+    #[test]
+    fn test_wrong_flag_is_not_trusted() {
+        // Counting the flag would make the mine at (2, 0) look safe
+        let hint = hint_ascii(
+            "f1*
+             011",
+        )
+        .unwrap();
+
+        assert_eq!(hint.position(), (0, 0));
+        assert!(matches!(hint, Hint::Safe { .. }));
+    }
+
+    #[test]
+    fn test_no_hint_without_hidden_cells() {
+        assert_eq!(hint_ascii("F1\n11"), None);
+    }
+}
@@ -0,0 +1,14 @@
+//! Reasoning over what a player can see: revealed numbers, flags and the total mine
+//! count. Nothing here reads the content of a hidden cell.
+
+pub mod autoplay;
+pub mod deduction;
+pub mod hint;
+pub mod probability;
+pub mod view;
+
+pub use autoplay::{autoplay, AutoplayOutcome};
+pub use deduction::{deduce, Deductions};
+pub use hint::{hint, Hint, Reason};
+pub use probability::{mine_probabilities, Probabilities};
+pub use view::{BoardView, VisibleCell};
@@ -0,0 +1,595 @@
+use super::deduction::{constraints, initial_knowledge, propagate, Constraint};
+use super::view::{BoardView, VisibleCell};
+
+/// The chance that each hidden cell is a mine, given everything the player can see.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Probabilities {
+    width: usize,
+    cells: Vec<Option<f64>>,
+    exact: bool,
+}
+
+impl Probabilities {
+    /// `None` for revealed and flagged cells.
+    pub fn get(&self, x: usize, y: usize) -> Option<f64> {
+        self.cells[y * self.width + x]
+    }
+
+    /// `false` when some group was too large to enumerate and its cells were estimated
+    /// from the numbers around them instead.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+/// Search steps shared by every group in one call. Enumeration is exponential in the
+/// size of a group, so a long frontier (worst case: a thin revealed strip across a
+/// large board) could otherwise take minutes. A step budget keeps the result
+/// deterministic on every machine.
+const SEARCH_BUDGET: u64 = 10_000_000;
+
+/// Exact mine probabilities for every hidden cell, assuming every layout that fits the
+/// visible numbers, the flags and the total mine count is equally likely.
+///
+/// Cells next to a revealed number (the frontier) are split into independent groups
+/// that share no number. Each group's layouts are enumerated and counted by how many
+/// mines they use; the groups are then combined, weighting each total by the number of
+/// ways to place the remaining mines in the cells no number touches. Returns `None`
+/// when no layout fits, e.g. when there are more flags than mines.
+///
+/// Once [`SEARCH_BUDGET`] runs out, the remaining groups are estimated from local
+/// constraint ratios and the result reports [`Probabilities::is_exact`] as `false`.
+/// Estimated cells are never 0 or 1, so they are never shown as certain.
+pub fn mine_probabilities(view: &BoardView) -> Option<Probabilities> {
+    let mut known = initial_knowledge(view);
+    propagate(view, &mut known);
+
+    let mines_known = known.iter().filter(|k| **k == Some(true)).count();
+    let mines_left = view.mine_count.checked_sub(mines_known)?;
+
+    let constraints = constraints(view, &known);
+    let groups = groups(view, &constraints);
+    let mut in_frontier = vec![false; view.len()];
+    for group in &groups {
+        for &cell in &group.cells {
+            in_frontier[cell] = true;
+        }
+    }
+    let interior: Vec<usize> = (0..view.len())
+        .filter(|&i| known[i].is_none() && !in_frontier[i])
+        .collect();
+
+    let mut budget = SEARCH_BUDGET;
+    let mut exact = true;
+    let counts: Vec<GroupCounts> = groups
+        .iter()
+        .map(|group| {
+            GroupCounts::enumerate(group, &mut budget).unwrap_or_else(|| {
+                exact = false;
+                GroupCounts::estimate(group)
+            })
+        })
+        .collect();
+    let all = counts
+        .iter()
+        .fold(vec![1.0], |total, group| convolve(&total, &group.layouts));
+    let weights = interior_weights(interior.len(), mines_left, all.len());
+    let total: f64 = all.iter().zip(&weights).map(|(n, w)| n * w).sum();
+    if total == 0.0 {
+        return None;
+    }
+
+    let mut cells: Vec<Option<f64>> = known
+        .iter()
+        .enumerate()
+        .map(|(index, k)| match (view.cell_at(index), k) {
+            (VisibleCell::Hidden, Some(true)) => Some(1.0),
+            (VisibleCell::Hidden, Some(false)) => Some(0.0),
+            _ => None,
+        })
+        .collect();
+
+    // Layouts of every other group, convolved, for each group in turn.
+    let mut before = vec![vec![1.0]];
+    for group in &counts {
+        before.push(convolve(before.last().unwrap(), &group.layouts));
+    }
+    let mut after = vec![1.0];
+    for (index, group) in counts.iter().enumerate().rev() {
+        let others = convolve(&before[index], &after);
+        // Weight of this group holding `k` mines, summed over the rest of the board.
+        let weight: Vec<f64> = (0..group.layouts.len())
+            .map(|k| {
+                others
+                    .iter()
+                    .enumerate()
+                    .map(|(rest, n)| n * weights.get(k + rest).copied().unwrap_or(0.0))
+                    .sum()
+            })
+            .collect();
+        for (&cell, by_mines) in groups[index].cells.iter().zip(&group.mine_layouts) {
+            let mine: f64 = by_mines.iter().zip(&weight).map(|(n, w)| n * w).sum();
+            cells[cell] = Some(mine / total);
+        }
+        after = convolve(&after, &group.layouts);
+    }
+
+    if !interior.is_empty() {
+        let expected: f64 = all
+            .iter()
+            .zip(&weights)
+            .enumerate()
+            .map(|(k, (n, w))| n * w * mines_left.saturating_sub(k) as f64)
+            .sum();
+        let probability = expected / total / interior.len() as f64;
+        for cell in interior {
+            cells[cell] = Some(probability);
+        }
+    }
+
+    Some(Probabilities {
+        width: view.width,
+        cells,
+        exact,
+    })
+}
+
+/// Frontier cells tied together by numbers they share, with those numbers.
+struct Group {
+    cells: Vec<usize>,
+    constraints: Vec<Constraint>,
+}
+
+fn groups(view: &BoardView, constraints: &[Constraint]) -> Vec<Group> {
+    let mut owner: Vec<Option<usize>> = vec![None; view.len()];
+    let mut parent: Vec<usize> = (0..constraints.len()).collect();
+    fn root(parent: &mut [usize], mut id: usize) -> usize {
+        while parent[id] != id {
+            parent[id] = parent[parent[id]];
+            id = parent[id];
+        }
+        id
+    }
+    for (id, constraint) in constraints.iter().enumerate() {
+        for &cell in &constraint.cells {
+            match owner[cell] {
+                Some(other) => {
+                    let (a, b) = (root(&mut parent, id), root(&mut parent, other));
+                    parent[a] = b;
+                }
+                None => owner[cell] = Some(id),
+            }
+        }
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    let mut group_of_root = vec![None; constraints.len()];
+    for (id, constraint) in constraints.iter().enumerate() {
+        let r = root(&mut parent, id);
+        let index = *group_of_root[r].get_or_insert_with(|| {
+            groups.push(Group {
+                cells: Vec::new(),
+                constraints: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[index].constraints.push(constraint.clone());
+    }
+    for group in &mut groups {
+        // Cells in the order the numbers reach them, so numbers close early in the search.
+        for constraint in &group.constraints {
+            for &cell in &constraint.cells {
+                if !group.cells.contains(&cell) {
+                    group.cells.push(cell);
+                }
+            }
+        }
+    }
+    groups
+}
+
+/// Layout counts for one group, indexed by how many mines the layout uses.
+struct GroupCounts {
+    layouts: Vec<f64>,
+    /// Per cell, in group order: layouts with a mine on that cell.
+    mine_layouts: Vec<Vec<f64>>,
+}
+
+impl GroupCounts {
+    /// Every layout of `group`, or `None` if that takes more than `budget` steps.
+    fn enumerate(group: &Group, budget: &mut u64) -> Option<Self> {
+        let n = group.cells.len();
+        let local: Vec<Vec<usize>> = group
+            .constraints
+            .iter()
+            .map(|c| {
+                c.cells
+                    .iter()
+                    .map(|cell| group.cells.iter().position(|g| g == cell).unwrap())
+                    .collect()
+            })
+            .collect();
+        let mut by_cell = vec![Vec::new(); n];
+        for (id, cells) in local.iter().enumerate() {
+            for &cell in cells {
+                by_cell[cell].push(id);
+            }
+        }
+
+        let mut search = Search {
+            by_cell,
+            target: group.constraints.iter().map(|c| c.mines).collect(),
+            placed: vec![0; local.len()],
+            open: local.iter().map(Vec::len).collect(),
+            layout: vec![false; n],
+            counts: GroupCounts {
+                layouts: vec![0.0; n + 1],
+                mine_layouts: vec![vec![0.0; n + 1]; n],
+            },
+            budget: *budget,
+        };
+        let finished = search.run(0, 0);
+        *budget = search.budget;
+        finished.then_some(search.counts)
+    }
+
+    /// A stand-in for a group too large to enumerate: each cell gets the average share of
+    /// mines the numbers around it still need, and the group's mine total is spread
+    /// binomially around their sum. Every number here still needs some but not all of
+    /// its cells to be mines (propagation settles the rest), so no estimate is 0 or 1.
+    fn estimate(group: &Group) -> Self {
+        let n = group.cells.len();
+        let chances: Vec<f64> = group
+            .cells
+            .iter()
+            .map(|cell| {
+                let ratios: Vec<f64> = group
+                    .constraints
+                    .iter()
+                    .filter(|c| c.cells.contains(cell))
+                    .map(|c| c.mines as f64 / c.cells.len() as f64)
+                    .collect();
+                ratios.iter().sum::<f64>() / ratios.len() as f64
+            })
+            .collect();
+        let mean = chances.iter().sum::<f64>() / n as f64;
+
+        let ln_factorial = ln_factorials(n);
+        let ln_layouts: Vec<f64> = (0..=n)
+            .map(|k| {
+                ln_factorial[n] - ln_factorial[k] - ln_factorial[n - k]
+                    + k as f64 * mean.ln()
+                    + (n - k) as f64 * (1.0 - mean).ln()
+            })
+            .collect();
+        let max = ln_layouts.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let layouts: Vec<f64> = ln_layouts.iter().map(|l| (l - max).exp()).collect();
+        let mine_layouts = chances
+            .iter()
+            .map(|chance| layouts.iter().map(|n| n * chance).collect())
+            .collect();
+        GroupCounts {
+            layouts,
+            mine_layouts,
+        }
+    }
+}
+
+/// Depth-first enumeration of mine/safe assignments, pruning as soon as a number can no
+/// longer be met.
+struct Search {
+    by_cell: Vec<Vec<usize>>,
+    target: Vec<usize>,
+    placed: Vec<usize>,
+    open: Vec<usize>,
+    layout: Vec<bool>,
+    counts: GroupCounts,
+    /// Steps left; each visited assignment costs one, and each layout found one per cell.
+    budget: u64,
+}
+
+impl Search {
+    /// `false` if the budget ran out before every layout was counted.
+    fn run(&mut self, cell: usize, mines: usize) -> bool {
+        if self.budget == 0 {
+            return false;
+        }
+        self.budget -= 1;
+        if cell == self.layout.len() {
+            self.budget = self.budget.saturating_sub(cell as u64);
+            self.counts.layouts[mines] += 1.0;
+            for (index, &mine) in self.layout.iter().enumerate() {
+                if mine {
+                    self.counts.mine_layouts[index][mines] += 1.0;
+                }
+            }
+            return true;
+        }
+
+        for mine in [false, true] {
+            self.layout[cell] = mine;
+            let mut fits = true;
+            for &id in &self.by_cell[cell] {
+                self.open[id] -= 1;
+                self.placed[id] += mine as usize;
+                fits &= self.placed[id] <= self.target[id]
+                    && self.placed[id] + self.open[id] >= self.target[id];
+            }
+            let finished = !fits || self.run(cell + 1, mines + mine as usize);
+            for &id in &self.by_cell[cell] {
+                self.open[id] += 1;
+                self.placed[id] -= mine as usize;
+            }
+            if !finished {
+                self.layout[cell] = false;
+                return false;
+            }
+        }
+        self.layout[cell] = false;
+        true
+    }
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// `C(interior, mines_left - k)` for each frontier mine total `k`, scaled so the largest
+/// is 1. Works in logs so that large boards don't overflow.
+fn interior_weights(interior: usize, mines_left: usize, totals: usize) -> Vec<f64> {
+    let ln_factorial = ln_factorials(interior);
+    let ln_weights: Vec<Option<f64>> = (0..totals)
+        .map(|k| {
+            let rest = mines_left.checked_sub(k)?;
+            (rest <= interior).then(|| {
+                ln_factorial[interior] - ln_factorial[rest] - ln_factorial[interior - rest]
+            })
+        })
+        .collect();
+    let max = ln_weights
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    ln_weights
+        .into_iter()
+        .map(|w| w.map_or(0.0, |w| (w - max).exp()))
+        .collect()
+}
+
+/// `ln(n!)` for every `n` up to `max`.
+fn ln_factorials(max: usize) -> Vec<f64> {
+    std::iter::once(0.0)
+        .chain((1..=max).scan(0.0, |sum, n| {
+            *sum += (n as f64).ln();
+            Some(*sum)
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::deduce;
+    use crate::GameBoard;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("cell should be hidden");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_fifty_fifty() {
+        let board = GameBoard::from_ascii("*.\n11").unwrap();
+        let probabilities = mine_probabilities(&BoardView::from_board(&board)).unwrap();
+
+        assert!(probabilities.is_exact());
+        assert_close(probabilities.get(0, 0), 0.5);
+        assert_close(probabilities.get(1, 0), 0.5);
+        assert_eq!(probabilities.get(0, 1), None);
+    }
+
+    #[test]
+    fn test_deduced_cells_are_certain() {
+        let board = GameBoard::from_ascii(
+            "..*.*..
+             ..121..",
+        )
+        .unwrap();
+        let probabilities = mine_probabilities(&BoardView::from_board(&board)).unwrap();
+
+        assert_close(probabilities.get(2, 0), 1.0);
+        assert_close(probabilities.get(3, 0), 0.0);
+    }
+
+    #[test]
+    fn test_interior_cells_weight_frontier_layouts() {
+        // a 1 b 1 c x y z: either b is the mine, or a and c are. With two mines in
+        // total, the first leaves one for the three interior cells (3 ways) and the
+        // second leaves none (1 way).
+        let mut view = BoardView::new(8, 1, 2);
+        view.set(1, 0, VisibleCell::Revealed(1));
+        view.set(3, 0, VisibleCell::Revealed(1));
+        let probabilities = mine_probabilities(&view).unwrap();
+
+        assert_close(probabilities.get(2, 0), 0.75);
+        assert_close(probabilities.get(0, 0), 0.25);
+        assert_close(probabilities.get(4, 0), 0.25);
+        assert_close(probabilities.get(6, 0), 0.25);
+    }
+
+    #[test]
+    fn test_unconstrained_board_is_uniform() {
+        let probabilities = mine_probabilities(&BoardView::new(9, 9, 10)).unwrap();
+
+        assert_close(probabilities.get(4, 4), 10.0 / 81.0);
+    }
+
+    #[test]
+    fn test_too_many_flags_has_no_layout() {
+        let mut view = BoardView::new(3, 1, 1);
+        view.set(0, 0, VisibleCell::Flagged);
+        view.set(2, 0, VisibleCell::Flagged);
+
+        assert_eq!(mine_probabilities(&view), None);
+    }
+
+    #[test]
+    fn test_long_frontier_is_estimated_within_budget() {
+        // A revealed strip between two hidden rows: one group of 600 cells with far more
+        // layouts than can be enumerated.
+        let mine = |x: usize, y: usize| y != 1 && (x * 7 + y) % 5 < 2;
+        let mines = (0..300)
+            .map(|x| mine(x, 0) as usize + mine(x, 2) as usize)
+            .sum();
+        let mut view = BoardView::new(300, 3, mines);
+        for x in 0..300_usize {
+            let around = (x.saturating_sub(1)..(x + 2).min(300))
+                .map(|n| mine(n, 0) as u8 + mine(n, 2) as u8)
+                .sum();
+            view.set(x, 1, VisibleCell::Revealed(around));
+        }
+        let start = std::time::Instant::now();
+        let probabilities = mine_probabilities(&view).unwrap();
+
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+        assert!(!probabilities.is_exact());
+        for x in 0..300 {
+            for y in [0, 2] {
+                let chance = probabilities.get(x, y).unwrap();
+                assert!(
+                    chance > 0.0 && chance < 1.0,
+                    "({}, {}) was {}",
+                    x,
+                    y,
+                    chance
+                );
+            }
+        }
+    }
+
+    /// Every consistent layout, counted directly.
+    fn brute_force(view: &BoardView) -> Vec<Option<f64>> {
+        let hidden: Vec<usize> = (0..view.len())
+            .filter(|&i| view.cell_at(i) == VisibleCell::Hidden)
+            .collect();
+        let flags = (0..view.len())
+            .filter(|&i| view.cell_at(i) == VisibleCell::Flagged)
+            .count();
+        let mut mine: Vec<bool> = (0..view.len())
+            .map(|i| view.cell_at(i) == VisibleCell::Flagged)
+            .collect();
+        let mut hits = vec![0.0; view.len()];
+        let mut total = 0.0;
+
+        fn place(
+            view: &BoardView,
+            hidden: &[usize],
+            left: usize,
+            mine: &mut Vec<bool>,
+            hits: &mut Vec<f64>,
+            total: &mut f64,
+        ) {
+            if left == 0 {
+                let fits = (0..view.len()).all(|i| match view.cell_at(i) {
+                    VisibleCell::Revealed(n) => {
+                        view.neighbors(i).filter(|&j| mine[j]).count() == n as usize
+                    }
+                    _ => true,
+                });
+                if fits {
+                    *total += 1.0;
+                    for (hit, &is_mine) in hits.iter_mut().zip(mine.iter()) {
+                        if is_mine {
+                            *hit += 1.0;
+                        }
+                    }
+                }
+                return;
+            }
+            for (offset, &cell) in hidden.iter().enumerate() {
+                mine[cell] = true;
+                place(view, &hidden[offset + 1..], left - 1, mine, hits, total);
+                mine[cell] = false;
+            }
+        }
+
+        place(
+            view,
+            &hidden,
+            view.mine_count - flags,
+            &mut mine,
+            &mut hits,
+            &mut total,
+        );
+        (0..view.len())
+            .map(|i| (view.cell_at(i) == VisibleCell::Hidden).then(|| hits[i] / total))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut checked = 0;
+        for seed in 0..40 {
+            let mut board = GameBoard::with_seed(6, 5, 6, seed);
+            board.start_game();
+            board.reveal_cell(0, 0);
+            if board.game_over {
+                continue;
+            }
+            let view = BoardView::from_board(&board);
+            let hidden = (0..view.len())
+                .filter(|&i| view.cell_at(i) == VisibleCell::Hidden)
+                .count();
+            if hidden > 20 {
+                continue;
+            }
+
+            let probabilities = mine_probabilities(&view).unwrap();
+            for (index, expected) in brute_force(&view).into_iter().enumerate() {
+                let (x, y) = view.position(index);
+                match expected {
+                    Some(expected) => assert_close(probabilities.get(x, y), expected),
+                    None => assert_eq!(probabilities.get(x, y), None),
+                }
+            }
+            checked += 1;
+        }
+        assert!(checked >= 5, "only {} boards checked", checked);
+    }
+
+    #[test]
+    fn test_expert_probabilities_sum_to_mines_left() {
+        let mut board = GameBoard::with_seed(30, 16, 99, 3);
+        board.start_game();
+        board.reveal_cell(15, 8);
+        let view = BoardView::from_board(&board);
+        let probabilities = mine_probabilities(&view).unwrap();
+        let deductions = deduce(&view);
+
+        let mut sum = 0.0;
+        for y in 0..16 {
+            for x in 0..30 {
+                sum += probabilities.get(x, y).unwrap_or(0.0);
+            }
+        }
+        assert!((sum - 99.0).abs() < 1e-6, "sum was {}", sum);
+        for &(x, y) in &deductions.safe {
+            assert_close(probabilities.get(x, y), 0.0);
+        }
+        for &(x, y) in &deductions.mines {
+            assert_close(probabilities.get(x, y), 1.0);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::game::{CellContent, CellState, GameBoard};
+
+/// One cell as the player sees it. Question marks carry no information, so they read
+/// as hidden; a revealed mine on a lost board reads as flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisibleCell {
+    Hidden,
+    Flagged,
+    Revealed(u8),
+}
+
+/// The player-visible state of a board, indexed row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardView {
+    pub width: usize,
+    pub height: usize,
+    pub mine_count: usize,
+    cells: Vec<VisibleCell>,
+}
+
+impl BoardView {
+    /// A view with every cell hidden.
+    pub fn new(width: usize, height: usize, mine_count: usize) -> Self {
+        Self {
+            width,
+            height,
+            mine_count,
+            cells: vec![VisibleCell::Hidden; width * height],
+        }
+    }
+
+    /// Reads a board's cell states, and the number of each revealed cell.
+    pub fn from_board(board: &GameBoard) -> Self {
+        let mut view = Self::new(board.width, board.height, board.mine_count);
+        for (y, row) in board.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let visible = match (cell.state, cell.content) {
+                    (CellState::Hidden | CellState::Questioned, _) => VisibleCell::Hidden,
+                    (CellState::Flagged, _) | (CellState::Revealed, CellContent::Mine) => {
+                        VisibleCell::Flagged
+                    }
+                    (CellState::Revealed, CellContent::Number(n)) => VisibleCell::Revealed(n),
+                    (CellState::Revealed, CellContent::Empty) => VisibleCell::Revealed(0),
+                };
+                view.set(x, y, visible);
+            }
+        }
+        view
+    }
+
+    /// The same view with every flag read as hidden, for reasoning that shouldn't trust
+    /// the player's flags.
+    pub fn without_flags(&self) -> Self {
+        let mut view = self.clone();
+        for cell in &mut view.cells {
+            if *cell == VisibleCell::Flagged {
+                *cell = VisibleCell::Hidden;
+            }
+        }
+        view
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> VisibleCell {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, cell: VisibleCell) {
+        self.cells[y * self.width + x] = cell;
+    }
+
+    pub(crate) fn cell_at(&self, index: usize) -> VisibleCell {
+        self.cells[index]
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub(crate) fn position(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    /// Indices of the up to eight cells around `index`.
+    pub(crate) fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = self.position(index);
+        let xs = x.saturating_sub(1)..=(x + 1).min(self.width - 1);
+        let ys = y.saturating_sub(1)..=(y + 1).min(self.height - 1);
+        ys.flat_map(move |ny| xs.clone().map(move |nx| ny * self.width + nx))
+            .filter(move |&n| n != index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_board_hides_unrevealed_content() {
+        let board = GameBoard::from_ascii(
+            "*2q
+             F2.
+             110",
+        )
+        .unwrap();
+        let view = BoardView::from_board(&board);
+
+        assert_eq!(view.mine_count, 2);
+        assert_eq!(view.get(0, 0), VisibleCell::Hidden);
+        assert_eq!(view.get(2, 0), VisibleCell::Hidden);
+        assert_eq!(view.get(0, 1), VisibleCell::Flagged);
+        assert_eq!(view.get(1, 0), VisibleCell::Revealed(2));
+        assert_eq!(view.get(2, 2), VisibleCell::Revealed(0));
+    }
+
+    #[test]
+    fn test_neighbors_stay_on_the_board() {
+        let view = BoardView::new(3, 2, 1);
+
+        let mut corner: Vec<usize> = view.neighbors(0).collect();
+        corner.sort();
+        assert_eq!(corner, vec![1, 3, 4]);
+        assert_eq!(view.neighbors(4).count(), 5);
+    }
+}
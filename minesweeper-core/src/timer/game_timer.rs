@@ -1,4 +1,4 @@
-use crate::utils::is_logging_enabled;
+use crate::debug::is_logging_enabled;
 use log::{debug, error, warn};
 use std::time::{Duration, Instant};
 
@@ -21,6 +21,14 @@ impl GameTimer {
         }
     }
 
+    /// A stopped timer that already shows `elapsed`, for resuming a saved game.
+    pub fn with_elapsed(elapsed: Duration) -> Self {
+        Self {
+            elapsed,
+            ..Self::new()
+        }
+    }
+
     pub fn start(&mut self) {
         if !self.is_running {
             if is_logging_enabled() {
@@ -89,6 +97,12 @@ impl GameTimer {
     }
 }
 
+impl Default for GameTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +117,17 @@ mod tests {
         assert_eq!(timer.get_elapsed(), Duration::ZERO);
     }
 
+    #[test]
+    fn test_timer_with_elapsed() {
+        let mut timer = GameTimer::with_elapsed(Duration::from_secs(42));
+
+        assert!(!timer.is_running());
+        assert_eq!(timer.get_elapsed(), Duration::from_secs(42));
+        timer.start();
+        thread::sleep(Duration::from_millis(5));
+        assert!(timer.get_elapsed() > Duration::from_secs(42));
+    }
+
     #[test]
     fn test_timer_start() {
         let mut timer = GameTimer::new();
@@ -1,10 +1,10 @@
-use crate::game::GameBoard;
-use crate::game_manager::{Difficulty, GameManager};
+use crate::saves::SaveFile;
 use crate::theme::apply_custom_style;
 use crate::ui::GameUI;
 use crate::utils::is_logging_enabled;
 use eframe::egui;
 use log::{debug, warn};
+use minesweeper_core::{GameBoard, GameManager, ReplayPlayer};
 
 pub struct MinesweeperApp {
     game_state: GameBoard,
@@ -13,33 +13,54 @@ pub struct MinesweeperApp {
 }
 
 impl MinesweeperApp {
-    pub fn new() -> Self {
+    pub fn new(
+        game_manager: GameManager,
+        game_state: GameBoard,
+        replay: Option<ReplayPlayer>,
+    ) -> Self {
         if is_logging_enabled() {
             debug!("Creating new MinesweeperApp");
         }
 
-        let difficulty = Difficulty::Beginner;
-        let (width, height, mines) = difficulty.get_dimensions();
+        let (width, height, mines) = game_manager.current_difficulty.get_dimensions();
 
         if is_logging_enabled() {
             debug!(
-                "Initializing game with difficulty: {:?}, dimensions: {}x{}, mines: {}",
-                difficulty, width, height, mines
+                "Initializing game with difficulty: {:?}, dimensions: {}x{}, mines: {}, seed: {:?}, placement: {}",
+                game_manager.current_difficulty,
+                width,
+                height,
+                mines,
+                game_manager.seed,
+                game_manager.placement
             );
         }
 
-        let mut app = Self {
-            game_state: GameBoard::new(width, height, mines),
-            game_manager: GameManager::new(),
-            ui: GameUI::new(),
-        };
+        let mut ui = GameUI::new();
+        if let Some(player) = replay {
+            ui.watch_replay(player);
+        }
 
-        app.game_manager.current_difficulty = difficulty;
-        app
+        Self {
+            game_state,
+            game_manager,
+            ui,
+        }
     }
 }
 
 impl eframe::App for MinesweeperApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        let Some(saved) = self.game_manager.save_game(&self.game_state) else {
+            return;
+        };
+        if let Err(e) = SaveFile::save(&saved) {
+            if is_logging_enabled() {
+                warn!("Could not save game on exit: {}", e);
+            }
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         apply_custom_style(ctx);
 
@@ -51,7 +72,11 @@ impl eframe::App for MinesweeperApp {
             }
         }
 
-        if self.game_state.game_started
+        // The replay viewer handles its own keys and must not touch the live game
+        let watching_replay = self.ui.is_watching_replay();
+
+        if !watching_replay
+            && self.game_state.game_started
             && !self.game_state.game_over
             && ctx.input(|i| i.key_pressed(egui::Key::Escape))
         {
@@ -71,6 +96,32 @@ impl eframe::App for MinesweeperApp {
             }
         }
 
+        if !watching_replay
+            && self.game_state.game_started
+            && (self.game_state.game_over || self.game_manager.timer.is_running())
+        {
+            let (redo, undo) = ctx.input_mut(|i| {
+                (
+                    i.consume_key(
+                        egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                        egui::Key::Z,
+                    ),
+                    i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+                )
+            });
+            if undo {
+                if is_logging_enabled() {
+                    debug!("Undo shortcut pressed");
+                }
+                self.game_manager.undo_move(&mut self.game_state);
+            } else if redo {
+                if is_logging_enabled() {
+                    debug!("Redo shortcut pressed");
+                }
+                self.game_manager.redo_move(&mut self.game_state);
+            }
+        }
+
         if self.game_state.game_over && self.game_manager.timer.is_running() {
             if is_logging_enabled() {
                 debug!(
@@ -79,6 +130,7 @@ impl eframe::App for MinesweeperApp {
                 );
             }
             self.game_manager.end_game(self.game_state.game_won);
+            self.ui.record_result(&self.game_state, &self.game_manager);
         }
 
         if !self.game_state.game_started && self.game_manager.timer.is_running() {
@@ -1,5 +0,0 @@
-pub mod board;
-pub mod cell;
-
-pub use board::GameBoard;
-pub use cell::{CellContent, CellState};
@@ -0,0 +1,291 @@
+//! Line-based protocol for bots. The host writes one JSON message per line to stdout;
+//! the bot answers each `board` with a move such as `reveal 3 4` on stdin. Moves go
+//! through the same `GameManager` as the window, and bots only ever see the visible
+//! board.
+
+pub mod protocol;
+
+pub use protocol::{Command, ErrorCode, HostMessage, ProtocolError};
+
+use crate::utils::is_logging_enabled;
+use log::debug;
+use minesweeper_core::{BoardView, CellContent, CellState, GameBoard, GameManager, ReplayAction};
+use std::io::{self, BufRead, Write};
+
+/// One game played over the protocol.
+pub struct Session {
+    game_manager: GameManager,
+    game_state: GameBoard,
+    moves: usize,
+    finished: bool,
+}
+
+impl Session {
+    /// Starts or resumes `game_state`. The protocol has no question marks, so flagging
+    /// always toggles between flagged and hidden whatever the window is set to.
+    pub fn new(mut game_manager: GameManager, mut game_state: GameBoard) -> Self {
+        game_manager.question_marks = false;
+        game_state.question_marks = false;
+        if !game_state.game_started {
+            game_state.start_game();
+            game_manager.start_game(game_manager.current_difficulty);
+        } else if !game_state.game_over {
+            game_manager.resume_game();
+        }
+        Self {
+            game_manager,
+            game_state,
+            moves: 0,
+            finished: false,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The visible board, sent at the start and after every move.
+    pub fn board_message(&self) -> HostMessage {
+        HostMessage::board(
+            &BoardView::from_board(&self.game_state),
+            self.game_state.flags_placed(),
+            self.moves,
+        )
+    }
+
+    /// Applies one line from the bot and returns the reply: the new board, the result
+    /// once the game is over, or an error that leaves the board as it was.
+    pub fn handle_line(&mut self, line: &str) -> Option<HostMessage> {
+        let command = match line.parse::<Command>() {
+            Ok(command) => command,
+            Err(error) => return Some(error.into()),
+        };
+        if is_logging_enabled() {
+            debug!("Bot command: {:?}", command);
+        }
+        if let Err(error) = self.check(command) {
+            return Some(error.into());
+        }
+
+        let action = match command {
+            Command::Reveal { x, y } => ReplayAction::Reveal { x, y },
+            Command::Flag { x, y } => ReplayAction::Flag { x, y },
+            Command::Chord { x, y } => ReplayAction::Chord { x, y },
+            Command::Quit => {
+                self.finished = true;
+                return None;
+            }
+        };
+        self.game_manager.apply_action(&mut self.game_state, action);
+        self.moves += 1;
+
+        if !self.game_state.game_over {
+            return Some(self.board_message());
+        }
+        self.game_manager.end_game(self.game_state.game_won);
+        self.finished = true;
+        Some(HostMessage::Result {
+            won: self.game_state.game_won,
+            time_ms: self.game_manager.timer.get_elapsed().as_millis() as u64,
+            three_bv: self.game_state.three_bv(),
+            moves: self.moves,
+            seed: self.game_state.seed,
+        })
+    }
+
+    /// Rejects moves that would do nothing, judged only by visible cell states.
+    fn check(&self, command: Command) -> Result<(), ProtocolError> {
+        let (Command::Reveal { x, y } | Command::Flag { x, y } | Command::Chord { x, y }) = command
+        else {
+            return Ok(());
+        };
+        let board = &self.game_state;
+        if board.game_over {
+            return Err(ProtocolError::new(ErrorCode::Illegal, "the game is over"));
+        }
+        if x >= board.width || y >= board.height {
+            return Err(ProtocolError::new(
+                ErrorCode::OutOfBounds,
+                format!(
+                    "({}, {}) is off the {}x{} board",
+                    x, y, board.width, board.height
+                ),
+            ));
+        }
+
+        let illegal = |message: &str| {
+            Err(ProtocolError::new(
+                ErrorCode::Illegal,
+                format!("({}, {}) {}", x, y, message),
+            ))
+        };
+        let state = board.board[y][x].state;
+        match command {
+            Command::Reveal { .. } => match state {
+                CellState::Revealed => illegal("is already revealed"),
+                CellState::Flagged => illegal("is flagged"),
+                CellState::Hidden | CellState::Questioned => Ok(()),
+            },
+            Command::Flag { .. } if state == CellState::Revealed => illegal("is already revealed"),
+            Command::Chord { .. } => {
+                let number = match (state, board.board[y][x].content) {
+                    (CellState::Revealed, CellContent::Number(n)) => n as usize,
+                    _ => return illegal("is not a revealed number"),
+                };
+                let rows = y.saturating_sub(1)..=(y + 1).min(board.height - 1);
+                let columns = x.saturating_sub(1)..=(x + 1).min(board.width - 1);
+                let around: Vec<CellState> = rows
+                    .flat_map(|ny| columns.clone().map(move |nx| (nx, ny)))
+                    .filter(|&cell| cell != (x, y))
+                    .map(|(nx, ny)| board.board[ny][nx].state)
+                    .collect();
+                let flags = around.iter().filter(|s| **s == CellState::Flagged).count();
+                if flags != number {
+                    illegal(&format!("has {} flags around a {}", flags, number))
+                } else if !around.iter().any(|s| s.is_revealable()) {
+                    illegal("has no hidden neighbours left")
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Plays one game over stdin/stdout, ending at the result, on `quit` or when the bot
+/// closes its input.
+pub fn run(game_manager: GameManager, game_state: GameBoard) -> io::Result<()> {
+    let mut session = Session::new(game_manager, game_state);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", session.board_message().to_json())?;
+    stdout.flush()?;
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if let Some(reply) = session.handle_line(&line) {
+            writeln!(stdout, "{}", reply.to_json())?;
+            stdout.flush()?;
+        }
+        if session.is_finished() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(text: &str) -> Session {
+        Session::new(GameManager::new(), GameBoard::from_ascii(text).unwrap())
+    }
+
+    fn error_code(reply: Option<HostMessage>) -> ErrorCode {
+        match reply {
+            Some(HostMessage::Error { code, .. }) => code,
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_board_never_shows_hidden_mines() {
+        let session = session(
+            "*..
+             ...",
+        );
+        let json = session.board_message().to_json();
+
+        assert!(json.contains(r#""rows":["...","..."]"#));
+        assert!(!json.contains('*'));
+    }
+
+    #[test]
+    fn test_moves_until_a_win() {
+        let mut session = session(
+            "*.
+             ..",
+        );
+        assert!(matches!(
+            session.handle_line("flag 0 0"),
+            Some(HostMessage::Board {
+                flags: 1,
+                moves: 1,
+                ..
+            })
+        ));
+        session.handle_line("reveal 1 0");
+        session.handle_line("reveal 0 1");
+        let result = session.handle_line("reveal 1 1");
+
+        assert!(matches!(
+            result,
+            Some(HostMessage::Result {
+                won: true,
+                three_bv: Some(3),
+                moves: 4,
+                ..
+            })
+        ));
+        assert!(session.is_finished());
+        assert_eq!(
+            error_code(session.handle_line("reveal 0 0")),
+            ErrorCode::Illegal
+        );
+    }
+
+    #[test]
+    fn test_flag_ignores_question_mark_setting() {
+        let mut game_manager = GameManager::new();
+        game_manager.question_marks = true;
+        let mut game_state = GameBoard::from_ascii("*..\n...").unwrap();
+        game_state.question_marks = true;
+        let mut session = Session::new(game_manager, game_state);
+
+        session.handle_line("flag 2 1");
+        session.handle_line("flag 2 1");
+
+        assert_eq!(session.game_state.board[1][2].state, CellState::Hidden);
+        assert_eq!(session.game_state.flags_placed(), 0);
+    }
+
+    #[test]
+    fn test_bad_lines_get_structured_errors() {
+        let mut session = session(
+            "*..
+             1..",
+        );
+
+        assert_eq!(
+            error_code(session.handle_line("explode")),
+            ErrorCode::Malformed
+        );
+        assert_eq!(
+            error_code(session.handle_line("reveal 9 0")),
+            ErrorCode::OutOfBounds
+        );
+        assert_eq!(
+            error_code(session.handle_line("reveal 0 1")),
+            ErrorCode::Illegal
+        );
+        assert_eq!(
+            error_code(session.handle_line("chord 0 1")),
+            ErrorCode::Illegal
+        );
+        assert_eq!(
+            error_code(session.handle_line("chord 1 0")),
+            ErrorCode::Illegal
+        );
+        session.handle_line("flag 1 0");
+        assert_eq!(
+            error_code(session.handle_line("reveal 1 0")),
+            ErrorCode::Illegal
+        );
+        assert!(!session.is_finished());
+
+        assert_eq!(session.handle_line("quit"), None);
+        assert!(session.is_finished());
+    }
+}
@@ -0,0 +1,191 @@
+use minesweeper_core::{BoardView, VisibleCell};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// One line the host writes to the bot.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    /// The board as the player sees it. Each row has one character per cell: `.` for
+    /// hidden, `F` for a flag and `0`-`8` for a revealed cell.
+    Board {
+        width: usize,
+        height: usize,
+        mines: usize,
+        flags: usize,
+        moves: usize,
+        rows: Vec<String>,
+    },
+    /// The game is over.
+    Result {
+        won: bool,
+        time_ms: u64,
+        three_bv: Option<usize>,
+        moves: usize,
+        seed: u64,
+    },
+    /// The last line was not applied; the board is unchanged.
+    Error { code: ErrorCode, message: String },
+}
+
+impl HostMessage {
+    /// A board message built only from what the player can see.
+    pub fn board(view: &BoardView, flags: usize, moves: usize) -> Self {
+        let rows = (0..view.height)
+            .map(|y| {
+                (0..view.width)
+                    .map(|x| match view.get(x, y) {
+                        VisibleCell::Hidden => '.',
+                        VisibleCell::Flagged => 'F',
+                        VisibleCell::Revealed(n) => char::from(b'0' + n),
+                    })
+                    .collect()
+            })
+            .collect();
+        HostMessage::Board {
+            width: view.width,
+            height: view.height,
+            mines: view.mine_count,
+            flags,
+            moves,
+            rows,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("host messages always serialize")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Not a command, or its arguments are not two coordinates.
+    Malformed,
+    /// The coordinates are off the board.
+    OutOfBounds,
+    /// The move can't be made on that cell, such as revealing a flag.
+    Illegal,
+}
+
+/// A rejected line, sent back as an `error` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ProtocolError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ProtocolError> for HostMessage {
+    fn from(error: ProtocolError) -> Self {
+        HostMessage::Error {
+            code: error.code,
+            message: error.message,
+        }
+    }
+}
+
+/// One line the bot writes to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Reveal { x: usize, y: usize },
+    Flag { x: usize, y: usize },
+    Chord { x: usize, y: usize },
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = ProtocolError;
+
+    /// Parses `reveal x y`, `flag x y`, `chord x y` or `quit`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return Err(ProtocolError::new(ErrorCode::Malformed, "empty command"));
+        };
+        let args: Vec<&str> = words.collect();
+
+        if name == "quit" {
+            if !args.is_empty() {
+                return Err(ProtocolError::new(
+                    ErrorCode::Malformed,
+                    "quit takes no arguments",
+                ));
+            }
+            return Ok(Command::Quit);
+        }
+        let make: fn(usize, usize) -> Command = match name {
+            "reveal" => |x, y| Command::Reveal { x, y },
+            "flag" => |x, y| Command::Flag { x, y },
+            "chord" => |x, y| Command::Chord { x, y },
+            _ => {
+                return Err(ProtocolError::new(
+                    ErrorCode::Malformed,
+                    format!("unknown command '{}'", name),
+                ))
+            }
+        };
+        let [x, y] = args[..] else {
+            return Err(ProtocolError::new(
+                ErrorCode::Malformed,
+                format!("{} takes two coordinates, x and y", name),
+            ));
+        };
+        let coordinate = |value: &str| {
+            value.parse::<usize>().map_err(|_| {
+                ProtocolError::new(
+                    ErrorCode::Malformed,
+                    format!("'{}' is not a coordinate", value),
+                )
+            })
+        };
+        Ok(make(coordinate(x)?, coordinate(y)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("reveal 3 4".parse(), Ok(Command::Reveal { x: 3, y: 4 }));
+        assert_eq!("  flag 0 1 ".parse(), Ok(Command::Flag { x: 0, y: 1 }));
+        assert_eq!("chord 2 2".parse(), Ok(Command::Chord { x: 2, y: 2 }));
+        assert_eq!("quit".parse(), Ok(Command::Quit));
+
+        for line in [
+            "",
+            "dig 1 1",
+            "reveal 1",
+            "reveal 1 2 3",
+            "flag -1 0",
+            "quit now",
+        ] {
+            let error = line.parse::<Command>().unwrap_err();
+            assert_eq!(error.code, ErrorCode::Malformed, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_messages_as_json() {
+        let mut view = BoardView::new(2, 1, 1);
+        view.set(1, 0, VisibleCell::Revealed(1));
+        assert_eq!(
+            HostMessage::board(&view, 0, 1).to_json(),
+            r#"{"type":"board","width":2,"height":1,"mines":1,"flags":0,"moves":1,"rows":[".1"]}"#
+        );
+        assert_eq!(
+            HostMessage::from(ProtocolError::new(ErrorCode::OutOfBounds, "off")).to_json(),
+            r#"{"type":"error","code":"out_of_bounds","message":"off"}"#
+        );
+    }
+}
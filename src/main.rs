@@ -1,17 +1,27 @@
 mod app;
-mod game;
-mod game_manager;
+mod headless;
+mod records;
+mod replays;
+mod saves;
+mod settings;
+mod simulate;
 mod theme;
-mod timer;
+mod tui;
 mod ui;
 mod utils;
 
 use crate::utils::{set_debug_enabled, set_show_mines_enabled};
 use app::MinesweeperApp;
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand};
 use eframe::{NativeOptions, Theme};
 use log::{error, info};
+use minesweeper_core::{
+    Difficulty, GameBoard, GameManager, PlacementPolicy, Replay, ReplayPlayer, SavedGame,
+};
+use settings::Settings;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 #[cfg(target_os = "macos")]
 #[allow(unexpected_cfgs)]
@@ -44,6 +54,42 @@ struct Args {
     debug: bool,
     #[arg(long)]
     show_mines: bool,
+    /// Seed for mine placement, to reproduce a board
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Cells kept free of mines on the first click: safe-cell, safe-opening or none
+    #[arg(long, default_value_t = PlacementPolicy::SafeOpening)]
+    placement: PlacementPolicy,
+    /// Custom board width (requires --height and --mines)
+    #[arg(long, requires_all = ["height", "mines"])]
+    width: Option<usize>,
+    /// Custom board height (requires --width and --mines)
+    #[arg(long, requires_all = ["width", "mines"])]
+    height: Option<usize>,
+    /// Custom mine count (requires --width and --height)
+    #[arg(long, requires_all = ["width", "height"])]
+    mines: Option<usize>,
+    /// Open a saved replay file in the replay viewer
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+    /// Start on a board drawn in the ASCII grid format (`*` mine, `.` safe, `F` flag,
+    /// digits for revealed cells)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["width", "height", "mines", "replay"])]
+    load_board: Option<PathBuf>,
+    /// Play in the terminal instead of opening a window
+    #[arg(long, conflicts_with = "replay")]
+    tui: bool,
+    /// Let a bot play over stdin/stdout with a line-delimited JSON protocol
+    #[arg(long, conflicts_with_all = ["replay", "tui"])]
+    headless: bool,
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Play many seeded games with the solver and report win rate, guesses and 3BV
+    Simulate(simulate::SimulateArgs),
 }
 
 fn main() -> Result<(), eframe::Error> {
@@ -68,6 +114,15 @@ fn main() -> Result<(), eframe::Error> {
     set_show_mines_enabled(args.show_mines);
     DEBUG_ENABLED.store(args.debug, Ordering::Relaxed);
 
+    if let Some(Commands::Simulate(simulate_args)) = &args.command {
+        if let Err(e) = simulate::run(simulate_args) {
+            Args::command()
+                .error(clap::error::ErrorKind::ValueValidation, e)
+                .exit();
+        }
+        return Ok(());
+    }
+
     if args.debug {
         env_logger::Builder::from_default_env()
             .filter_level(log::LevelFilter::Debug)
@@ -96,16 +151,108 @@ fn main() -> Result<(), eframe::Error> {
         info!("Creating application window");
     }
 
+    let mut game_manager = GameManager::new();
+    game_manager.seed = args.seed;
+    game_manager.placement = args.placement;
+    game_manager.question_marks = Settings::load().question_marks;
+    if let (Some(width), Some(height), Some(mines)) = (args.width, args.height, args.mines) {
+        match Difficulty::custom(width, height, mines) {
+            Ok(difficulty) => game_manager.current_difficulty = difficulty,
+            Err(e) => Args::command()
+                .error(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!("invalid custom board: {}", e),
+                )
+                .exit(),
+        }
+    }
+
+    let replay = args.replay.as_deref().map(|path| {
+        let player = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| text.parse::<Replay>().map_err(|e| e.to_string()))
+            .and_then(|replay| ReplayPlayer::new(replay).map_err(|e| e.to_string()));
+        match player {
+            Ok(player) => player,
+            Err(e) => Args::command()
+                .error(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!("invalid replay {}: {}", path.display(), e),
+                )
+                .exit(),
+        }
+    });
+
+    let game_state = match args.load_board.as_deref() {
+        Some(path) => {
+            let board = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| GameBoard::from_ascii(&text).map_err(|e| e.to_string()));
+            match board {
+                Ok(mut board) => {
+                    // The layout is known in advance, so it can't set a best time
+                    board.assisted = true;
+                    let (width, height, mines) = (board.width, board.height, board.mine_count);
+                    let difficulty = match Difficulty::custom(width, height, mines) {
+                        Ok(difficulty) => difficulty,
+                        Err(e) => {
+                            // Still playable as loaded; reset_game won't repeat it
+                            eprintln!(
+                                "Note: {} is outside the custom board limits ({}), so new games will be Beginner",
+                                path.display(),
+                                e
+                            );
+                            Difficulty::Custom {
+                                width,
+                                height,
+                                mines,
+                            }
+                        }
+                    };
+                    game_manager.restore_game(SavedGame {
+                        difficulty,
+                        board,
+                        elapsed: Duration::ZERO,
+                        replay: None,
+                    })
+                }
+                Err(e) => Args::command()
+                    .error(
+                        clap::error::ErrorKind::ValueValidation,
+                        format!("invalid board {}: {}", path.display(), e),
+                    )
+                    .exit(),
+            }
+        }
+        None => game_manager.create_board(),
+    };
+
+    if args.headless {
+        if let Err(e) = headless::run(game_manager, game_state) {
+            eprintln!("Headless error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.tui {
+        if let Err(e) = tui::run(game_manager, game_state) {
+            eprintln!("Terminal error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let result = eframe::run_native(
         "Minesweeper",
         options,
-        Box::new(|_cc| {
+        Box::new(move |_cc| {
             if DEBUG_ENABLED.load(Ordering::Relaxed) {
                 info!("Initializing MinesweeperApp");
             }
             #[cfg(target_os = "macos")]
             set_macos_app_menu_title();
-            Box::new(MinesweeperApp::new())
+            Box::new(MinesweeperApp::new(game_manager, game_state, replay))
         }),
     );
 
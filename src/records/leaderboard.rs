@@ -0,0 +1,283 @@
+//! Best times per difficulty, stored as plain text:
+//!
+//! ```text
+//! minesweeper-records 1
+//! expert 95310 1760659200 1.72 Alice
+//! custom-20x16x50 40120 1760745600 - Bob
+//! ```
+//!
+//! Each record line is the difficulty key, the time in milliseconds, the date as Unix
+//! seconds, 3BV/s (`-` when unknown) and the player name, which runs to the end of the
+//! line. Lines that don't parse are skipped.
+
+use crate::utils::{data_dir, is_logging_enabled, write_atomically};
+use log::{debug, warn};
+use minesweeper_core::Difficulty;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const LEADERBOARD_FILE: &str = "leaderboard.txt";
+const HEADER: &str = "minesweeper-records 1";
+
+/// Records kept for each difficulty.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// One won game on the leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub time_ms: u64,
+    /// Unix seconds.
+    pub date: u64,
+    pub three_bv_per_second: Option<f64>,
+    pub name: String,
+}
+
+/// Where a new record landed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+    /// Zero-based place on the leaderboard, or `None` if it was too slow to make it.
+    pub rank: Option<usize>,
+    /// Faster than any earlier win by the same player on this difficulty.
+    pub personal_best: bool,
+}
+
+/// The fastest wins per difficulty. Custom boards are kept apart by their dimensions
+/// and mine count.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Leaderboard {
+    records: BTreeMap<String, Vec<Record>>,
+}
+
+impl Leaderboard {
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(LEADERBOARD_FILE))
+    }
+
+    /// Loads the saved records, or an empty leaderboard if there are none.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match Self::load_from(&path) {
+            Ok(leaderboard) => leaderboard,
+            Err(e) => {
+                if is_logging_enabled() && e.kind() != io::ErrorKind::NotFound {
+                    warn!("Could not read leaderboard from {}: {}", path.display(), e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        self.save_to(&path)
+    }
+
+    /// Replaces the file atomically, so a crash leaves either the old records or the
+    /// new ones.
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if is_logging_enabled() {
+            debug!("Saving leaderboard to {}", path.display());
+        }
+        write_atomically(path, &self.to_string())
+    }
+
+    /// The records for `difficulty`, fastest first.
+    pub fn records(&self, difficulty: Difficulty) -> &[Record] {
+        self.records
+            .get(&difficulty_key(difficulty))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds `record`, keeping the fastest [`LEADERBOARD_SIZE`]. A tie goes after the
+    /// records already there.
+    pub fn insert(&mut self, difficulty: Difficulty, record: Record) -> Standing {
+        let records = self.records.entry(difficulty_key(difficulty)).or_default();
+        let rank = records.partition_point(|r| r.time_ms <= record.time_ms);
+        if rank >= LEADERBOARD_SIZE {
+            return Standing {
+                rank: None,
+                personal_best: false,
+            };
+        }
+        // Records only leave the board when faster ones push them off, so any faster
+        // win by this player is still on it
+        let personal_best = !records[..rank].iter().any(|r| r.name == record.name);
+        records.insert(rank, record);
+        records.truncate(LEADERBOARD_SIZE);
+        Standing {
+            rank: Some(rank),
+            personal_best,
+        }
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut leaderboard = Self::default();
+        for line in contents.lines() {
+            let Some((key, record)) = parse_record(line.trim()) else {
+                continue;
+            };
+            let records = leaderboard.records.entry(key.to_string()).or_default();
+            let rank = records.partition_point(|r| r.time_ms <= record.time_ms);
+            records.insert(rank, record);
+            records.truncate(LEADERBOARD_SIZE);
+        }
+        leaderboard
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for (key, records) in &self.records {
+            for record in records {
+                let rate = match record.three_bv_per_second {
+                    Some(rate) => format!("{:.2}", rate),
+                    None => "-".to_string(),
+                };
+                writeln!(
+                    f,
+                    "{} {} {} {} {}",
+                    key, record.time_ms, record.date, rate, record.name
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_record(line: &str) -> Option<(&str, Record)> {
+    let mut fields = line.splitn(5, ' ');
+    let key = fields.next()?;
+    let time_ms = fields.next()?.parse().ok()?;
+    let date = fields.next()?.parse().ok()?;
+    let three_bv_per_second = match fields.next()? {
+        "-" => None,
+        rate => Some(rate.parse().ok()?),
+    };
+    let name = fields.next()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let record = Record {
+        time_ms,
+        date,
+        three_bv_per_second,
+        name: name.to_string(),
+    };
+    Some((key, record))
+}
+
+fn difficulty_key(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Beginner => "beginner".to_string(),
+        Difficulty::Intermediate => "intermediate".to_string(),
+        Difficulty::Expert => "expert".to_string(),
+        Difficulty::Custom {
+            width,
+            height,
+            mines,
+        } => format!("custom-{}x{}x{}", width, height, mines),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(time_ms: u64, name: &str) -> Record {
+        Record {
+            time_ms,
+            date: 1_760_659_200,
+            three_bv_per_second: Some(1.5),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_insert_ranks_and_personal_bests() {
+        let mut leaderboard = Leaderboard::default();
+
+        let first = leaderboard.insert(Difficulty::Expert, record(90_000, "Ann"));
+        assert_eq!(first.rank, Some(0));
+        assert!(first.personal_best);
+
+        let slower = leaderboard.insert(Difficulty::Expert, record(95_000, "Ann"));
+        assert_eq!(slower.rank, Some(1));
+        assert!(!slower.personal_best);
+
+        let other = leaderboard.insert(Difficulty::Expert, record(92_000, "Bo"));
+        assert_eq!(other.rank, Some(1));
+        assert!(other.personal_best);
+
+        assert!(leaderboard.records(Difficulty::Beginner).is_empty());
+    }
+
+    #[test]
+    fn test_keeps_only_the_fastest() {
+        let mut leaderboard = Leaderboard::default();
+        for i in 0..LEADERBOARD_SIZE as u64 {
+            leaderboard.insert(Difficulty::Beginner, record(10_000 + i, "Ann"));
+        }
+
+        let too_slow = leaderboard.insert(Difficulty::Beginner, record(20_000, "Bo"));
+        assert_eq!(too_slow.rank, None);
+        assert!(!too_slow.personal_best);
+
+        leaderboard.insert(Difficulty::Beginner, record(5_000, "Bo"));
+        let records = leaderboard.records(Difficulty::Beginner);
+        assert_eq!(records.len(), LEADERBOARD_SIZE);
+        assert_eq!(records[0].name, "Bo");
+        assert_eq!(records[LEADERBOARD_SIZE - 1].time_ms, 10_008);
+    }
+
+    #[test]
+    fn test_round_trip_skips_bad_lines() {
+        let mut leaderboard = Leaderboard::default();
+        let custom = Difficulty::Custom {
+            width: 20,
+            height: 16,
+            mines: 50,
+        };
+        leaderboard.insert(custom, record(40_000, "Ann Lee"));
+        leaderboard.insert(
+            Difficulty::Expert,
+            Record {
+                three_bv_per_second: None,
+                ..record(90_000, "Bo")
+            },
+        );
+
+        let text = format!("{}expert not-a-time 0 - Cy\nexpert 1 2 3\n", leaderboard);
+        assert!(text.contains("custom-20x16x50 40000 1760659200 1.50 Ann Lee"));
+        assert_eq!(Leaderboard::parse(&text), leaderboard);
+    }
+
+    #[test]
+    fn test_save_replaces_file_atomically() {
+        let path = std::env::temp_dir()
+            .join(format!("minesweeper-records-{}", std::process::id()))
+            .join(LEADERBOARD_FILE);
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(Difficulty::Beginner, record(12_000, "Ann"));
+        leaderboard.save_to(&path).unwrap();
+        leaderboard.insert(Difficulty::Beginner, record(11_000, "Ann"));
+        leaderboard.save_to(&path).unwrap();
+
+        let loaded = Leaderboard::load_from(&path).unwrap();
+        let files = fs::read_dir(path.parent().unwrap()).unwrap().count();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, leaderboard);
+        assert_eq!(files, 1);
+    }
+}
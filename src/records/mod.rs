@@ -0,0 +1,3 @@
+pub mod leaderboard;
+
+pub use leaderboard::{Leaderboard, Record, Standing};
@@ -0,0 +1,3 @@
+pub mod replay_files;
+
+pub use replay_files::save_replay;
@@ -0,0 +1,57 @@
+use crate::utils::{data_dir, is_logging_enabled};
+use log::debug;
+use minesweeper_core::Replay;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const REPLAYS_DIR: &str = "replays";
+
+pub fn replays_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(REPLAYS_DIR))
+}
+
+/// Saves `replay` to a new timestamped file in the replays directory.
+pub fn save_replay(replay: &Replay) -> io::Result<PathBuf> {
+    let dir = replays_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let path = dir.join(format!("replay-{}-seed{}.txt", millis, replay.seed));
+    save_replay_to(replay, &path)?;
+    Ok(path)
+}
+
+pub fn save_replay_to(replay: &Replay, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if is_logging_enabled() {
+        debug!("Saving replay to {}", path.display());
+    }
+    fs::write(path, replay.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minesweeper_core::{GameBoard, ReplayAction};
+
+    #[test]
+    fn test_save_replay_to_file() {
+        let path = std::env::temp_dir()
+            .join(format!("minesweeper-replays-{}", std::process::id()))
+            .join("replay.txt");
+        let mut replay = Replay::new(&GameBoard::with_seed(9, 9, 10, 8));
+        replay.record(120, ReplayAction::Reveal { x: 1, y: 2 });
+
+        save_replay_to(&replay, &path).unwrap();
+        let loaded: Replay = fs::read_to_string(&path).unwrap().parse().unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, replay);
+    }
+}
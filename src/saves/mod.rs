@@ -0,0 +1,3 @@
+pub mod save_file;
+
+pub use save_file::SaveFile;
@@ -0,0 +1,96 @@
+use crate::utils::{data_dir, is_logging_enabled, write_atomically};
+use log::{debug, warn};
+use minesweeper_core::SavedGame;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SAVE_FILE: &str = "savegame.txt";
+
+/// The single slot holding the last unfinished game.
+pub struct SaveFile;
+
+impl SaveFile {
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(SAVE_FILE))
+    }
+
+    pub fn exists() -> bool {
+        Self::path().is_some_and(|path| path.is_file())
+    }
+
+    /// Loads the saved game, or `None` if there is none or it can't be read.
+    pub fn load() -> Option<SavedGame> {
+        let path = Self::path()?;
+        match Self::load_from(&path) {
+            Ok(saved) => Some(saved),
+            Err(e) => {
+                if is_logging_enabled() && e.kind() != io::ErrorKind::NotFound {
+                    warn!("Could not read saved game from {}: {}", path.display(), e);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<SavedGame> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(saved: &SavedGame) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        Self::save_to(saved, &path)
+    }
+
+    /// Replaces the file atomically, so a crash while saving can't lose the game.
+    pub fn save_to(saved: &SavedGame, path: &Path) -> io::Result<()> {
+        if is_logging_enabled() {
+            debug!("Saving game to {}", path.display());
+        }
+        write_atomically(path, &saved.to_string())
+    }
+
+    /// Removes the save once it has been continued, so it can't be resumed twice.
+    pub fn delete() {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Err(e) = fs::remove_file(&path) {
+            if is_logging_enabled() && e.kind() != io::ErrorKind::NotFound {
+                warn!("Could not remove saved game {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minesweeper_core::{Difficulty, GameManager};
+
+    #[test]
+    fn test_save_and_load_file() {
+        let path = std::env::temp_dir()
+            .join(format!("minesweeper-save-{}", std::process::id()))
+            .join(SAVE_FILE);
+        let mut manager = GameManager::new();
+        let mut board = manager.create_board();
+        manager.start_game(Difficulty::Beginner);
+        board.start_game();
+        manager.reveal_cell(&mut board, 4, 4);
+        let saved = manager.save_game(&board).unwrap();
+
+        SaveFile::save_to(&saved, &path).unwrap();
+        SaveFile::save_to(&saved, &path).unwrap();
+        let loaded = SaveFile::load_from(&path).unwrap();
+        let files = fs::read_dir(path.parent().unwrap()).unwrap().count();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(files, 1);
+        assert_eq!(loaded.board.board, board.board);
+        assert_eq!(loaded.difficulty, Difficulty::Beginner);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::utils::{config_dir, is_logging_enabled};
+use log::{debug, warn};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SETTINGS_FILE: &str = "settings.txt";
+const DEFAULT_PLAYER_NAME: &str = "Player";
+
+/// User preferences that survive restarts, stored as `key=value` lines.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Settings {
+    pub question_marks: bool,
+    /// Name put on leaderboard records; blank means the default.
+    pub player_name: String,
+}
+
+impl Settings {
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join(SETTINGS_FILE))
+    }
+
+    /// Loads the saved settings, falling back to defaults if there are none.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+
+        match Self::load_from(&path) {
+            Ok(settings) => settings,
+            Err(e) => {
+                if is_logging_enabled() && e.kind() != io::ErrorKind::NotFound {
+                    warn!("Could not read settings from {}: {}", path.display(), e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// The name to record wins under.
+    pub fn player_name(&self) -> &str {
+        match self.player_name.trim() {
+            "" => DEFAULT_PLAYER_NAME,
+            name => name,
+        }
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no configuration directory"))?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if is_logging_enabled() {
+            debug!("Saving settings to {}", path.display());
+        }
+        fs::write(path, self.serialize())
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut settings = Self::default();
+
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key.trim() {
+                "question_marks" => settings.question_marks = value.trim() == "true",
+                "player_name" => settings.player_name = value.trim().to_string(),
+                _ => {}
+            }
+        }
+
+        settings
+    }
+
+    fn serialize(&self) -> String {
+        format!(
+            "question_marks={}\nplayer_name={}\n",
+            self.question_marks,
+            self.player_name.trim()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = Settings {
+            question_marks: true,
+            player_name: "Ann Lee".to_string(),
+        };
+
+        assert_eq!(Settings::parse(&settings.serialize()), settings);
+    }
+
+    #[test]
+    fn test_settings_ignore_unknown_and_malformed_lines() {
+        let settings = Settings::parse("theme=dark\nnonsense\n question_marks = true \n");
+
+        assert!(settings.question_marks);
+        assert_eq!(settings.player_name(), "Player");
+    }
+
+    #[test]
+    fn test_settings_save_and_load_file() {
+        let path = std::env::temp_dir()
+            .join(format!("minesweeper-settings-{}", std::process::id()))
+            .join(SETTINGS_FILE);
+        let settings = Settings {
+            question_marks: true,
+            ..Settings::default()
+        };
+
+        settings.save_to(&path).unwrap();
+        let loaded = Settings::load_from(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, settings);
+    }
+}
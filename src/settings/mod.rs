@@ -0,0 +1,3 @@
+pub mod app_settings;
+
+pub use app_settings::Settings;
@@ -0,0 +1,298 @@
+//! `simulate` subcommand: plays many seeded games with the solver and reports how the
+//! generator settings affect win rate, guessing and board difficulty.
+
+use clap::ValueEnum;
+use minesweeper_core::game::no_guess::is_no_guess_practical;
+use minesweeper_core::{autoplay, AutoplayOutcome, Difficulty, GameBoard, PlacementPolicy};
+use rayon::prelude::*;
+use std::fmt;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Preset {
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SimulateArgs {
+    /// Number of games to play
+    #[arg(long, default_value_t = 1000)]
+    pub games: u64,
+    /// Seed of the first game; game `i` uses seed + i
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    #[arg(long, value_enum, default_value_t = Preset::Expert)]
+    pub difficulty: Preset,
+    /// Custom board width, replacing --difficulty (requires --height and --mines)
+    #[arg(long, requires_all = ["height", "mines"])]
+    pub width: Option<usize>,
+    /// Custom board height (requires --width and --mines)
+    #[arg(long, requires_all = ["width", "mines"])]
+    pub height: Option<usize>,
+    /// Custom mine count (requires --width and --height)
+    #[arg(long, requires_all = ["width", "height"])]
+    pub mines: Option<usize>,
+    /// Cells kept free of mines on the first click: safe-cell, safe-opening or none
+    #[arg(long, default_value_t = PlacementPolicy::SafeOpening)]
+    pub placement: PlacementPolicy,
+    /// Only generate boards that can be cleared without guessing
+    #[arg(long)]
+    pub no_guess: bool,
+    /// Worker threads; one per core by default
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+/// The board settings every simulated game shares.
+#[derive(Debug, Clone, Copy)]
+pub struct Setup {
+    pub difficulty: Difficulty,
+    pub placement: PlacementPolicy,
+    pub no_guess: bool,
+}
+
+/// One simulated game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedGame {
+    pub outcome: AutoplayOutcome,
+    /// No-guess generation gave up and the game was played on a layout that may need
+    /// guessing.
+    pub no_guess_fallback: bool,
+}
+
+impl Setup {
+    /// Plays the game for `seed`, first clicking the middle of the board.
+    pub fn play(&self, seed: u64) -> SimulatedGame {
+        let (width, height, mines) = self.difficulty.get_dimensions();
+        let mut board = GameBoard::with_seed(width, height, mines, seed);
+        board.placement = self.placement;
+        board.no_guess = self.no_guess;
+        let outcome = autoplay(&mut board, (width / 2, height / 2));
+        SimulatedGame {
+            outcome,
+            // Generation clears the flag when it runs out of attempts
+            no_guess_fallback: self.no_guess && !board.no_guess,
+        }
+    }
+
+    /// Plays every seed in `seeds` in parallel. Games come back in seed order, so the
+    /// same range always gives the same results.
+    pub fn play_all(&self, seeds: std::ops::Range<u64>) -> Vec<SimulatedGame> {
+        seeds.into_par_iter().map(|seed| self.play(seed)).collect()
+    }
+}
+
+/// Totals over a batch of games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub wins: usize,
+    pub guesses: usize,
+    /// Games played on a fallback layout because no no-guess one was found.
+    pub no_guess_fallbacks: usize,
+    /// Sorted 3BV of every game.
+    pub three_bv: Vec<usize>,
+}
+
+impl Summary {
+    pub fn new(games: &[SimulatedGame]) -> Self {
+        let mut three_bv: Vec<usize> = games.iter().filter_map(|g| g.outcome.three_bv).collect();
+        three_bv.sort_unstable();
+        Self {
+            games: games.len(),
+            wins: games.iter().filter(|g| g.outcome.won).count(),
+            guesses: games.iter().map(|g| g.outcome.guesses).sum(),
+            no_guess_fallbacks: games.iter().filter(|g| g.no_guess_fallback).count(),
+            three_bv,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.wins as f64, self.games)
+    }
+
+    pub fn mean_guesses(&self) -> f64 {
+        ratio(self.guesses as f64, self.games)
+    }
+
+    pub fn mean_three_bv(&self) -> f64 {
+        ratio(
+            self.three_bv.iter().sum::<usize>() as f64,
+            self.three_bv.len(),
+        )
+    }
+
+    /// The 3BV below which `percent` of the games fall, by nearest rank.
+    pub fn three_bv_percentile(&self, percent: usize) -> Option<usize> {
+        if self.three_bv.is_empty() {
+            return None;
+        }
+        let rank = (percent * self.three_bv.len()).div_ceil(100).max(1);
+        Some(self.three_bv[rank - 1])
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  Win rate:      {:.1}% ({}/{})",
+            self.win_rate() * 100.0,
+            self.wins,
+            self.games
+        )?;
+        writeln!(f, "  Guesses/game:  {:.2}", self.mean_guesses())?;
+        if self.no_guess_fallbacks > 0 {
+            writeln!(
+                f,
+                "  Fallbacks:     {} boards had no no-guess layout and may need guessing",
+                self.no_guess_fallbacks
+            )?;
+        }
+        let percentiles: Vec<String> = [0, 10, 25, 50, 75, 90, 100]
+            .iter()
+            .filter_map(|&p| {
+                let value = self.three_bv_percentile(p)?;
+                Some(match p {
+                    0 => format!("min {}", value),
+                    50 => format!("median {}", value),
+                    100 => format!("max {}", value),
+                    _ => format!("p{} {}", p, value),
+                })
+            })
+            .collect();
+        write!(
+            f,
+            "  3BV:           mean {:.1}, {}",
+            self.mean_three_bv(),
+            percentiles.join(", ")
+        )
+    }
+}
+
+fn ratio(total: f64, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f64
+    }
+}
+
+/// Runs the batch described by `args` and prints the report.
+pub fn run(args: &SimulateArgs) -> Result<(), String> {
+    let difficulty = match (args.width, args.height, args.mines) {
+        (Some(width), Some(height), Some(mines)) => Difficulty::custom(width, height, mines)
+            .map_err(|e| format!("invalid custom board: {}", e))?,
+        _ => match args.difficulty {
+            Preset::Beginner => Difficulty::Beginner,
+            Preset::Intermediate => Difficulty::Intermediate,
+            Preset::Expert => Difficulty::Expert,
+        },
+    };
+    let (width, height, mines) = difficulty.get_dimensions();
+    if args.no_guess && !is_no_guess_practical(width, height, mines) {
+        eprintln!(
+            "Warning: this board is too large or dense for no-guess generation, so most games will fall back to layouts that may need guessing"
+        );
+    }
+    let setup = Setup {
+        difficulty,
+        placement: args.placement,
+        no_guess: args.no_guess,
+    };
+    let end = args
+        .seed
+        .checked_add(args.games)
+        .ok_or("the seed range overflows u64")?;
+
+    let mut pool = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = args.threads {
+        pool = pool.num_threads(threads);
+    }
+    let pool = pool.build().map_err(|e| e.to_string())?;
+
+    let started = Instant::now();
+    let outcomes = pool.install(|| setup.play_all(args.seed..end));
+    let elapsed = started.elapsed();
+
+    println!(
+        "{} games of {} (seeds {}..{}, placement {}{})",
+        args.games,
+        difficulty.name(),
+        args.seed,
+        end,
+        args.placement,
+        if args.no_guess { ", no-guess" } else { "" }
+    );
+    println!("{}", Summary::new(&outcomes));
+    println!(
+        "  Throughput:    {:.1} games/s on {} thread{} ({:.2} s)",
+        args.games as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        pool.current_num_threads(),
+        if pool.current_num_threads() == 1 {
+            ""
+        } else {
+            "s"
+        },
+        elapsed.as_secs_f64()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_statistics() {
+        let outcome = |won, guesses, three_bv| SimulatedGame {
+            outcome: AutoplayOutcome {
+                won,
+                guesses,
+                three_bv: Some(three_bv),
+            },
+            no_guess_fallback: false,
+        };
+        let summary = Summary::new(&[
+            outcome(true, 0, 30),
+            outcome(false, 3, 10),
+            outcome(true, 1, 20),
+            outcome(false, 2, 40),
+        ]);
+
+        assert_eq!(summary.win_rate(), 0.5);
+        assert_eq!(summary.mean_guesses(), 1.5);
+        assert_eq!(summary.mean_three_bv(), 25.0);
+        assert_eq!(summary.three_bv_percentile(0), Some(10));
+        assert_eq!(summary.three_bv_percentile(50), Some(20));
+        assert_eq!(summary.three_bv_percentile(100), Some(40));
+        assert_eq!(Summary::new(&[]).three_bv_percentile(50), None);
+    }
+
+    #[test]
+    fn test_seed_range_is_deterministic() {
+        let setup = Setup {
+            difficulty: Difficulty::Beginner,
+            placement: PlacementPolicy::SafeOpening,
+            no_guess: false,
+        };
+
+        let first = setup.play_all(100..140);
+        assert_eq!(first, setup.play_all(100..140));
+        assert_eq!(first[7], setup.play(107));
+
+        // Dense enough that some no-guess searches run out of attempts
+        let dense = Setup {
+            difficulty: Difficulty::custom(9, 9, 26).unwrap(),
+            placement: PlacementPolicy::SafeOpening,
+            no_guess: true,
+        };
+        let first = dense.play_all(0..8);
+        assert_eq!(first, dense.play_all(0..8));
+        assert_eq!(first[5], dense.play(5));
+        let fallbacks = Summary::new(&first).no_guess_fallbacks;
+        assert!((1..8).contains(&fallbacks));
+    }
+}
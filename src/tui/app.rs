@@ -0,0 +1,213 @@
+use crate::saves::SaveFile;
+use crate::utils::is_logging_enabled;
+use crossterm::event::KeyCode;
+use log::{debug, warn};
+use minesweeper_core::{CellState, Difficulty, GameBoard, GameManager, ReplayAction, SavedGame};
+
+/// The terminal game: the board, the manager timing it, and the cell under the cursor.
+pub struct TuiApp {
+    pub game_state: GameBoard,
+    pub game_manager: GameManager,
+    pub cursor: (usize, usize),
+    quit: bool,
+}
+
+impl TuiApp {
+    /// Starts `game_state` unless it is already under way, as a restored save is.
+    pub fn new(mut game_manager: GameManager, mut game_state: GameBoard) -> Self {
+        if !game_state.game_started {
+            game_state.start_game();
+            game_manager.start_game(game_manager.current_difficulty);
+        }
+        let cursor = (game_state.width / 2, game_state.height / 2);
+        Self {
+            game_state,
+            game_manager,
+            cursor,
+            quit: false,
+        }
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Started, not over, and the timer stopped.
+    pub fn is_paused(&self) -> bool {
+        self.game_state.game_started
+            && !self.game_state.game_over
+            && !self.game_manager.timer.is_running()
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc => self.toggle_pause(),
+            KeyCode::Char('n') => self.new_game(self.game_manager.current_difficulty),
+            KeyCode::Char('1') => self.new_game(Difficulty::Beginner),
+            KeyCode::Char('2') => self.new_game(Difficulty::Intermediate),
+            KeyCode::Char('3') => self.new_game(Difficulty::Expert),
+            _ if self.is_paused() => {}
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(0, 1),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                let (x, y) = self.cursor;
+                // Like a left click: a revealed number chords, anything else is revealed
+                let action = if self.game_state.board[y][x].state == CellState::Revealed {
+                    ReplayAction::Chord { x, y }
+                } else {
+                    ReplayAction::Reveal { x, y }
+                };
+                self.play(action);
+            }
+            KeyCode::Char('f') => {
+                let (x, y) = self.cursor;
+                self.play(ReplayAction::Flag { x, y });
+            }
+            KeyCode::Char('u') => self.play(ReplayAction::Undo),
+            _ => {}
+        }
+    }
+
+    /// Stops the timer once the game is decided, as the window does every frame.
+    pub fn tick(&mut self) {
+        if self.game_state.game_over && self.game_manager.timer.is_running() {
+            if is_logging_enabled() {
+                debug!(
+                    "Game over detected, ending timer. Won: {}",
+                    self.game_state.game_won
+                );
+            }
+            self.game_manager.end_game(self.game_state.game_won);
+        }
+    }
+
+    /// The game to save on exit: one in progress that has had its first reveal.
+    pub fn game_to_save(&self) -> Option<SavedGame> {
+        if self.game_state.first_click {
+            return None;
+        }
+        self.game_manager.save_game(&self.game_state)
+    }
+
+    /// Saves a game in progress so either frontend can continue it. A save that is still
+    /// there wasn't continued by this session, so it is left alone.
+    pub fn save_on_exit(&self) {
+        let Some(saved) = self.game_to_save() else {
+            return;
+        };
+        if SaveFile::exists() {
+            if is_logging_enabled() {
+                debug!("Keeping the existing saved game instead of saving this one");
+            }
+            return;
+        }
+        if let Err(e) = SaveFile::save(&saved) {
+            if is_logging_enabled() {
+                warn!("Could not save game on exit: {}", e);
+            }
+        }
+    }
+
+    fn play(&mut self, action: ReplayAction) {
+        // Undo can still take back a finished game
+        if self.game_manager.timer.is_running() || self.game_state.game_over {
+            self.game_manager.apply_action(&mut self.game_state, action);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        if !self.game_state.game_started || self.game_state.game_over {
+            return;
+        }
+        if self.game_manager.timer.is_running() {
+            self.game_manager.pause_game();
+        } else {
+            self.game_manager.resume_game();
+        }
+    }
+
+    fn new_game(&mut self, difficulty: Difficulty) {
+        self.game_manager.current_difficulty = difficulty;
+        self.game_manager.reset_game(&mut self.game_state);
+        self.game_state.start_game();
+        self.game_manager.start_game(difficulty);
+        self.cursor = (self.game_state.width / 2, self.game_state.height / 2);
+    }
+
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (x, y) = self.cursor;
+        self.cursor = (
+            x.saturating_add_signed(dx).min(self.game_state.width - 1),
+            y.saturating_add_signed(dy).min(self.game_state.height - 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(text: &str) -> TuiApp {
+        TuiApp::new(GameManager::new(), GameBoard::from_ascii(text).unwrap())
+    }
+
+    #[test]
+    fn test_keys_move_reveal_and_flag() {
+        let mut app = app("*..
+             ...
+             ...");
+        assert_eq!(app.cursor, (1, 1));
+
+        app.handle_key(KeyCode::Char(' '));
+        assert_eq!(app.game_state.board[1][1].state, CellState::Revealed);
+        app.handle_key(KeyCode::Up);
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Left);
+        assert_eq!(app.cursor, (0, 0));
+        app.handle_key(KeyCode::Char('f'));
+        assert_eq!(app.game_state.board[0][0].state, CellState::Flagged);
+        assert!(app.game_manager.replay().is_some());
+    }
+
+    #[test]
+    fn test_escape_pauses_and_blocks_moves() {
+        let mut app = app("*..
+             ...");
+        app.handle_key(KeyCode::Esc);
+        assert!(app.is_paused());
+
+        app.handle_key(KeyCode::Char(' '));
+        assert_eq!(app.game_state.board[1][1].state, CellState::Hidden);
+        app.handle_key(KeyCode::Esc);
+        app.handle_key(KeyCode::Char(' '));
+        assert_eq!(app.game_state.board[1][1].state, CellState::Revealed);
+    }
+
+    #[test]
+    fn test_nothing_to_save_before_the_first_reveal() {
+        let mut game_manager = GameManager::new();
+        game_manager.seed = Some(8);
+        let game_state = game_manager.create_board();
+        let mut app = TuiApp::new(game_manager, game_state);
+        assert!(app.game_to_save().is_none());
+
+        app.handle_key(KeyCode::Enter);
+        assert!(app.game_to_save().is_some());
+    }
+
+    #[test]
+    fn test_losing_stops_the_timer() {
+        let mut app = app("*.");
+        app.handle_key(KeyCode::Left);
+        app.handle_key(KeyCode::Enter);
+        app.tick();
+
+        assert!(app.game_state.game_over);
+        assert!(!app.game_manager.timer.is_running());
+        app.handle_key(KeyCode::Esc);
+        assert!(!app.is_paused());
+    }
+}
@@ -0,0 +1,78 @@
+//! Terminal frontend for playing over SSH or anywhere else the window can't open. It
+//! drives the same `GameManager`, so times, replays and saves match the window's.
+
+pub mod app;
+mod render;
+
+pub use app::TuiApp;
+
+use crate::saves::SaveFile;
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use minesweeper_core::{GameBoard, GameManager};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io;
+use std::time::Duration;
+
+/// How long to wait for a key before redrawing the clock.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Plays in the terminal until the player quits. A game in progress is saved on exit.
+pub fn run(mut game_manager: GameManager, mut game_state: GameBoard) -> io::Result<()> {
+    // Continue the saved game like the window's welcome screen does, unless a board
+    // was loaded. The save is only removed once it has been restored.
+    if !game_state.game_started {
+        if let Some(saved) = SaveFile::load() {
+            game_state = game_manager.restore_game(saved);
+            SaveFile::delete();
+        }
+    }
+    let mut app = TuiApp::new(game_manager, game_state);
+
+    let _terminal_mode = TerminalMode::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    while !app.should_quit() {
+        terminal.draw(|frame| render::draw(frame, &app))?;
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+        app.tick();
+    }
+
+    app.save_on_exit();
+    Ok(())
+}
+
+/// Raw mode on the alternate screen, restored when dropped or on a panic.
+struct TerminalMode;
+
+impl TerminalMode {
+    fn enter() -> io::Result<Self> {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            Self::restore();
+            previous_hook(panic_info);
+        }));
+        enable_raw_mode()?;
+        crossterm::execute!(io::stdout(), EnterAlternateScreen, crossterm::cursor::Hide)?;
+        Ok(Self)
+    }
+
+    fn restore() {
+        let _ = disable_raw_mode();
+        let _ = crossterm::execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+    }
+}
+
+impl Drop for TerminalMode {
+    fn drop(&mut self) {
+        Self::restore();
+    }
+}
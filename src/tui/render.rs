@@ -0,0 +1,197 @@
+use super::app::TuiApp;
+use crate::utils::format_time;
+use minesweeper_core::{CellContent, CellState, GameBoard};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+/// Terminal columns per cell, so the board looks roughly square.
+const CELL_WIDTH: u16 = 2;
+
+pub fn draw(frame: &mut Frame, app: &TuiApp) {
+    let [status, board, help] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.size());
+
+    frame.render_widget(Paragraph::new(status_line(app)), status);
+
+    let title = if app.is_paused() {
+        " Paused "
+    } else if app.game_state.game_won {
+        " Cleared! "
+    } else if app.game_state.game_over {
+        " Game over "
+    } else {
+        " Minesweeper "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(board);
+    frame.render_widget(block, board);
+    if app.is_paused() {
+        // The board stays hidden while the clock is stopped, as in the window
+        frame.render_widget(
+            Paragraph::new("Paused - press Esc to resume").alignment(Alignment::Center),
+            inner,
+        );
+    } else {
+        frame.render_widget(Paragraph::new(board_lines(app, inner)), inner);
+    }
+
+    let keys = if app.game_state.game_over {
+        "n: new game  1/2/3: beginner/intermediate/expert  u: undo  q: quit"
+    } else {
+        "arrows/hjkl: move  space: reveal  f: flag  u: undo  esc: pause  n: new game  q: quit"
+    };
+    frame.render_widget(
+        Paragraph::new(keys).style(Style::default().fg(Color::DarkGray)),
+        help,
+    );
+}
+
+fn status_line(app: &TuiApp) -> Line<'static> {
+    let board = &app.game_state;
+    let mut spans = vec![
+        Span::styled(
+            app.game_manager.current_difficulty.name(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("   Mines left: {}", board.mines_remaining())),
+        Span::raw(format!(
+            "   Time: {}",
+            format_time(app.game_manager.timer.get_elapsed())
+        )),
+        Span::styled(
+            format!("   Seed: {}", board.seed),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if board.assisted {
+        spans.push(Span::styled(
+            "   (assisted)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The part of the board that fits in `area`, scrolled to keep the cursor in view.
+fn board_lines(app: &TuiApp, area: Rect) -> Vec<Line<'static>> {
+    let board = &app.game_state;
+    let columns = (area.width / CELL_WIDTH).max(1) as usize;
+    let rows = area.height.max(1) as usize;
+    let left = scroll_offset(app.cursor.0, columns, board.width);
+    let top = scroll_offset(app.cursor.1, rows, board.height);
+
+    (top..board.height.min(top + rows))
+        .map(|y| {
+            let spans = (left..board.width.min(left + columns))
+                .map(|x| {
+                    let (symbol, mut style) = cell_symbol(board, x, y);
+                    if (x, y) == app.cursor && !board.game_over {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    Span::styled(format!("{} ", symbol), style)
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// First index to show so that `cursor` stays near the middle of `visible` cells.
+fn scroll_offset(cursor: usize, visible: usize, total: usize) -> usize {
+    if total <= visible {
+        return 0;
+    }
+    cursor.saturating_sub(visible / 2).min(total - visible)
+}
+
+fn cell_symbol(board: &GameBoard, x: usize, y: usize) -> (char, Style) {
+    let cell = &board.board[y][x];
+    let lost = board.game_over && !board.game_won;
+    match (cell.state, cell.content) {
+        (CellState::Flagged, CellContent::Mine) => ('F', Style::default().fg(Color::Red)),
+        // Crossed out once the game is lost
+        (CellState::Flagged, _) if lost => (
+            'X',
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        (CellState::Flagged, _) => ('F', Style::default().fg(Color::Red)),
+        (CellState::Questioned, _) => ('?', Style::default().fg(Color::Gray)),
+        (CellState::Hidden, _) => ('·', Style::default().fg(Color::DarkGray)),
+        (CellState::Revealed, CellContent::Mine) => (
+            '*',
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (CellState::Revealed, CellContent::Empty) => (' ', Style::default()),
+        (CellState::Revealed, CellContent::Number(n)) => {
+            let color = match n {
+                1 => Color::Blue,
+                2 => Color::Green,
+                3 => Color::Red,
+                4 => Color::LightBlue,
+                5 => Color::LightRed,
+                6 => Color::Cyan,
+                7 => Color::Yellow,
+                _ => Color::Gray,
+            };
+            (
+                char::from(b'0' + n),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minesweeper_core::GameManager;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn test_draw_lost_board() {
+        let mut app = TuiApp::new(
+            GameManager::new(),
+            GameBoard::from_ascii(
+                "*f.
+                 ...",
+            )
+            .unwrap(),
+        );
+        app.cursor = (0, 0);
+        app.handle_key(crossterm::event::KeyCode::Char(' '));
+        let mut terminal = Terminal::new(TestBackend::new(60, 6)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(text.contains("Game over"));
+        assert!(text.contains("* X ·"));
+    }
+
+    #[test]
+    fn test_scroll_keeps_cursor_visible() {
+        assert_eq!(scroll_offset(5, 20, 9), 0);
+        assert_eq!(scroll_offset(3, 10, 30), 0);
+        assert_eq!(scroll_offset(15, 10, 30), 10);
+        assert_eq!(scroll_offset(29, 10, 30), 20);
+    }
+}
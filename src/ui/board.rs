@@ -1,9 +1,8 @@
-use crate::game::GameBoard;
-use crate::game::{CellContent, CellState};
-use crate::game_manager::GameManager;
+use super::heatmap::{lerp, risk_color, CellRisk, Heatmap};
 use crate::theme::Palette;
 use crate::utils::is_show_mines_enabled;
 use egui::{Color32, Painter, Rect};
+use minesweeper_core::{CellContent, CellState, GameBoard, ReplayAction};
 
 pub struct BoardRenderer {
     cell_size: f32,
@@ -14,12 +13,17 @@ impl BoardRenderer {
         Self { cell_size: 30.0 }
     }
 
+    /// Draws the board and returns the action the player clicked, if any. With
+    /// `input_enabled` off clicks are ignored, and `cursor` marks a cell with a ring.
+    /// Hidden cells are tinted by `heatmap` while it is enabled.
     pub fn render(
         &mut self,
         ui: &mut egui::Ui,
-        game_state: &mut GameBoard,
-        game_manager: &GameManager,
-    ) {
+        game_state: &GameBoard,
+        input_enabled: bool,
+        cursor: Option<(usize, usize)>,
+        heatmap: &mut Heatmap,
+    ) -> Option<ReplayAction> {
         let toolbar_height = 60.0;
         let padding = 40.0;
         let available_width = ui.available_width() - padding;
@@ -48,6 +52,10 @@ impl BoardRenderer {
         let response = ui.allocate_rect(board_rect, egui::Sense::click());
 
         let painter = ui.painter();
+        let palette = Palette::default();
+        let show_percentages = heatmap.shows_percentages();
+        let probabilities = heatmap.probabilities(game_state);
+        let approximate = probabilities.is_some_and(|p| !p.is_exact());
 
         for y in 0..game_state.height {
             for x in 0..game_state.width {
@@ -59,11 +67,34 @@ impl BoardRenderer {
                     egui::vec2(self.cell_size, self.cell_size),
                 );
 
-                self.render_cell(painter, game_state, x, y, cell_rect);
+                let risk = probabilities.and_then(|p| p.get(x, y)).map(CellRisk::new);
+                self.render_cell(
+                    painter,
+                    game_state,
+                    x,
+                    y,
+                    cell_rect,
+                    risk,
+                    show_percentages,
+                    approximate,
+                );
             }
         }
 
-        if !game_state.game_over && game_manager.timer.is_running() {
+        if let Some((x, y)) = cursor {
+            let center = egui::pos2(
+                board_start_x + (x as f32 + 0.5) * self.cell_size,
+                board_start_y + (y as f32 + 0.5) * self.cell_size,
+            );
+            painter.circle_stroke(
+                center,
+                self.cell_size * 0.45,
+                egui::Stroke::new(3.0, palette.accent),
+            );
+        }
+
+        let mut action = None;
+        if input_enabled {
             if let Some(click_pos) = response.interact_pointer_pos() {
                 let relative_x = click_pos.x - board_start_x;
                 let relative_y = click_pos.y - board_start_y;
@@ -73,10 +104,38 @@ impl BoardRenderer {
                     let cell_y = (relative_y / self.cell_size) as usize;
 
                     if cell_x < game_state.width && cell_y < game_state.height {
-                        if response.ctx.input(|i| i.pointer.secondary_clicked()) {
-                            game_state.toggle_flag(cell_x, cell_y);
-                        } else if response.ctx.input(|i| i.pointer.primary_clicked()) {
-                            game_state.reveal_cell(cell_x, cell_y);
+                        let (primary, secondary, middle, primary_down, secondary_down) =
+                            response.ctx.input(|i| {
+                                (
+                                    i.pointer.primary_clicked(),
+                                    i.pointer.secondary_clicked(),
+                                    i.pointer.button_clicked(egui::PointerButton::Middle),
+                                    i.pointer.primary_down(),
+                                    i.pointer.secondary_down(),
+                                )
+                            });
+                        let is_revealed =
+                            matches!(game_state.board[cell_y][cell_x].state, CellState::Revealed);
+
+                        // Chord on middle-click, left+right, or left-click on a revealed number
+                        if middle
+                            || (secondary && primary_down)
+                            || (primary && (secondary_down || is_revealed))
+                        {
+                            action = Some(ReplayAction::Chord {
+                                x: cell_x,
+                                y: cell_y,
+                            });
+                        } else if secondary {
+                            action = Some(ReplayAction::Flag {
+                                x: cell_x,
+                                y: cell_y,
+                            });
+                        } else if primary {
+                            action = Some(ReplayAction::Reveal {
+                                x: cell_x,
+                                y: cell_y,
+                            });
                         }
                     }
                 }
@@ -84,8 +143,13 @@ impl BoardRenderer {
         }
 
         self.cell_size = original_cell_size;
+        action
     }
 
+    /// Draws one cell. `risk` is the heatmap reading for a hidden cell, with the
+    /// percentage written on it when `show_percentage` is set, marked with `~` when the
+    /// probabilities are `approximate`.
+    #[allow(clippy::too_many_arguments)]
     fn render_cell(
         &self,
         painter: &Painter,
@@ -93,12 +157,20 @@ impl BoardRenderer {
         x: usize,
         y: usize,
         rect: Rect,
+        risk: Option<CellRisk>,
+        show_percentage: bool,
+        approximate: bool,
     ) {
         let cell = &game_state.board[y][x];
         let palette = Palette::default();
         let show_mines = is_show_mines_enabled();
+        // Flags on safe cells are crossed out once the game is lost
+        let wrong_flag = game_state.game_over
+            && !game_state.game_won
+            && matches!(cell.state, CellState::Flagged)
+            && !matches!(cell.content, CellContent::Mine);
 
-        let (text, bg_color, text_color) = match cell.state {
+        let (mut text, mut bg_color, mut text_color) = match cell.state {
             CellState::Hidden => {
                 // Show mines in debug mode even when hidden
                 if show_mines && matches!(cell.content, CellContent::Mine) {
@@ -112,6 +184,7 @@ impl BoardRenderer {
                 }
             }
             CellState::Flagged => ("🚩".to_string(), palette.surface_3, palette.text),
+            CellState::Questioned => ("❓".to_string(), palette.surface_3, palette.text_muted),
             CellState::Revealed => match cell.content {
                 CellContent::Empty => ("".to_string(), palette.surface_0, palette.text),
                 CellContent::Number(n) => {
@@ -132,16 +205,45 @@ impl BoardRenderer {
             },
         };
 
+        let mut stroke =
+            egui::Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 255, 255, 20));
+        let mut text_size = self.cell_size * 0.6;
+        let debug_mine = show_mines && matches!(cell.content, CellContent::Mine);
+        if let Some(risk) = risk.filter(|_| cell.state.is_revealable() && !debug_mine) {
+            match risk {
+                CellRisk::Safe => {
+                    bg_color = lerp(bg_color, palette.success, 0.7);
+                    stroke = egui::Stroke::new(2.0, palette.success);
+                    if text.is_empty() {
+                        text = "✔".to_string();
+                        text_color = Color32::WHITE;
+                    }
+                }
+                CellRisk::Mine => {
+                    bg_color = lerp(bg_color, palette.danger, 0.7);
+                    stroke = egui::Stroke::new(2.0, palette.danger);
+                    if text.is_empty() {
+                        text = "✖".to_string();
+                        text_color = Color32::WHITE;
+                    }
+                }
+                CellRisk::Chance(probability) => {
+                    bg_color = lerp(bg_color, risk_color(probability), 0.55);
+                    if show_percentage && text.is_empty() {
+                        let mark = if approximate { "~" } else { "" };
+                        text = format!("{}{:.0}%", mark, probability * 100.0);
+                        text_color = palette.text;
+                        text_size = self.cell_size * 0.3;
+                    }
+                }
+            }
+        }
+
         painter.rect_filled(rect, egui::Rounding::same(2.0), bg_color);
 
-        painter.rect_stroke(
-            rect,
-            egui::Rounding::same(2.0),
-            egui::Stroke::new(1.0, Color32::from_rgba_premultiplied(255, 255, 255, 20)),
-        );
+        painter.rect_stroke(rect, egui::Rounding::same(2.0), stroke);
 
         if !text.is_empty() {
-            let text_size = self.cell_size * 0.6;
             painter.text(
                 rect.center(),
                 egui::Align2::CENTER_CENTER,
@@ -150,5 +252,12 @@ impl BoardRenderer {
                 text_color,
             );
         }
+
+        if wrong_flag {
+            let cross = rect.shrink(self.cell_size * 0.15);
+            let stroke = egui::Stroke::new(2.5, palette.danger);
+            painter.line_segment([cross.left_top(), cross.right_bottom()], stroke);
+            painter.line_segment([cross.right_top(), cross.left_bottom()], stroke);
+        }
     }
 }
@@ -0,0 +1,144 @@
+use crate::theme::Palette;
+use egui::Color32;
+use minesweeper_core::{mine_probabilities, BoardView, GameBoard, Probabilities};
+
+/// Probabilities this close to 0 or 1 are treated as certain.
+const CERTAIN: f64 = 1e-9;
+
+/// The mine probability overlay: whether it is shown, and the probabilities for the
+/// last visible state it was asked about.
+pub struct Heatmap {
+    enabled: bool,
+    show_percentages: bool,
+    cache: Option<(BoardView, Option<Probabilities>)>,
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            show_percentages: false,
+            cache: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn shows_percentages(&self) -> bool {
+        self.show_percentages
+    }
+
+    pub fn toggle_percentages(&mut self) {
+        self.show_percentages = !self.show_percentages;
+    }
+
+    /// Probabilities for `board`, recomputed only when what the player can see has
+    /// changed. `None` while the overlay is off or no layout fits the board. Flags may
+    /// be wrong, so they are read as hidden cells.
+    pub fn probabilities(&mut self, board: &GameBoard) -> Option<&Probabilities> {
+        if !self.enabled {
+            return None;
+        }
+        let view = BoardView::from_board(board).without_flags();
+        if !matches!(&self.cache, Some((cached, _)) if *cached == view) {
+            let probabilities = mine_probabilities(&view);
+            self.cache = Some((view, probabilities));
+        }
+        self.cache.as_ref().and_then(|(_, p)| p.as_ref())
+    }
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a hidden cell should look under the overlay.
+pub enum CellRisk {
+    Safe,
+    Mine,
+    Chance(f64),
+}
+
+impl CellRisk {
+    pub fn new(probability: f64) -> Self {
+        if probability < CERTAIN {
+            CellRisk::Safe
+        } else if probability > 1.0 - CERTAIN {
+            CellRisk::Mine
+        } else {
+            CellRisk::Chance(probability)
+        }
+    }
+}
+
+/// Green for safe through amber to red for a mine.
+pub fn risk_color(probability: f64) -> Color32 {
+    let palette = Palette::default();
+    let amber = Color32::from_rgb(234, 179, 8);
+    let probability = probability.clamp(0.0, 1.0) as f32;
+    if probability < 0.5 {
+        lerp(palette.success, amber, probability * 2.0)
+    } else {
+        lerp(amber, palette.danger, probability * 2.0 - 1.0)
+    }
+}
+
+/// Mixes `from` toward `to` by `t` in `0.0..=1.0`.
+pub fn lerp(from: Color32, to: Color32, t: f32) -> Color32 {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color32::from_rgb(
+        mix(from.r(), to.r()),
+        mix(from.g(), to.g()),
+        mix(from.b(), to.b()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_color_scale() {
+        let palette = Palette::default();
+
+        assert_eq!(risk_color(0.0), palette.success);
+        assert_eq!(risk_color(0.5), Color32::from_rgb(234, 179, 8));
+        assert_eq!(risk_color(1.0), palette.danger);
+    }
+
+    #[test]
+    fn test_probabilities_only_while_enabled() {
+        let mut board = GameBoard::from_layout(3, 3, &[(0, 0)]).unwrap();
+        board.start_game();
+        board.reveal_cell(2, 2);
+        let mut heatmap = Heatmap::new();
+
+        assert!(heatmap.probabilities(&board).is_none());
+        heatmap.toggle();
+        assert_eq!(heatmap.probabilities(&board).unwrap().get(0, 0), Some(1.0));
+    }
+
+    #[test]
+    fn test_wrong_flag_does_not_make_a_mine_look_safe() {
+        // Counting the flag on (0, 0) would satisfy the 1 and clear the mine at (2, 0)
+        let board = GameBoard::from_ascii(
+            "f1*
+             011",
+        )
+        .unwrap();
+        let mut heatmap = Heatmap::new();
+        heatmap.toggle();
+
+        let probabilities = heatmap.probabilities(&board).unwrap();
+        assert_eq!(probabilities.get(2, 0), Some(1.0));
+        assert_eq!(probabilities.get(0, 0), Some(0.0));
+    }
+}
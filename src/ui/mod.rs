@@ -1,14 +1,30 @@
 pub mod board;
+pub mod heatmap;
 pub mod overlays;
 pub mod welcome;
 
 pub use board::BoardRenderer;
+pub use heatmap::Heatmap;
 pub use overlays::{GameOverOverlay, PauseOverlay, WinOverlay};
 pub use welcome::WelcomeScreen;
 
-use crate::game::GameBoard;
-use crate::game_manager::GameManager;
+use crate::records::{Leaderboard, Record};
+use crate::settings::Settings;
+use crate::utils::is_logging_enabled;
 use egui::Ui;
+use log::{debug, warn};
+use minesweeper_core::{
+    BoardView, GameBoard, GameManager, GameStats, Hint, ReplayAction, ReplayPlayer,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const REPLAY_SPEEDS: [f64; 5] = [0.5, 1.0, 2.0, 4.0, 8.0];
+
+/// A toolbar button the player clicked.
+enum ToolbarAction {
+    History(ReplayAction),
+    Hint,
+}
 
 pub struct GameUI {
     board_renderer: BoardRenderer,
@@ -16,6 +32,11 @@ pub struct GameUI {
     game_over_overlay: GameOverOverlay,
     win_overlay: WinOverlay,
     pause_overlay: PauseOverlay,
+    replay_player: Option<ReplayPlayer>,
+    heatmap: Heatmap,
+    /// The hint on show, with the board it was given for.
+    hint: Option<(Hint, BoardView)>,
+    leaderboard: Leaderboard,
 }
 
 impl GameUI {
@@ -26,7 +47,58 @@ impl GameUI {
             game_over_overlay: GameOverOverlay::new(),
             win_overlay: WinOverlay::new(),
             pause_overlay: PauseOverlay::new(),
+            replay_player: None,
+            heatmap: Heatmap::new(),
+            hint: None,
+            leaderboard: Leaderboard::load(),
+        }
+    }
+
+    /// Puts a just-finished game on the leaderboard if it was won without help, and
+    /// tells the win screen how it placed.
+    pub fn record_result(&mut self, game_state: &GameBoard, game_manager: &GameManager) {
+        if !game_state.game_won || game_state.assisted {
+            self.win_overlay.set_standing(None);
+            return;
+        }
+        let elapsed = game_manager.timer.get_elapsed();
+        let record = Record {
+            time_ms: elapsed.as_millis() as u64,
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            three_bv_per_second: GameStats::new(game_state, elapsed)
+                .and_then(|stats| stats.three_bv_per_second()),
+            name: Settings::load().player_name().to_string(),
+        };
+
+        // Another window may have added records since this one started
+        self.leaderboard = Leaderboard::load();
+        let standing = self
+            .leaderboard
+            .insert(game_manager.current_difficulty, record);
+        if is_logging_enabled() {
+            debug!("Recorded win: {:?}", standing);
         }
+        if let Err(e) = self.leaderboard.save() {
+            if is_logging_enabled() {
+                warn!("Could not save leaderboard: {}", e);
+            }
+        }
+        self.win_overlay.set_standing(Some(standing));
+    }
+
+    /// Switches to replay mode until the viewer is closed.
+    pub fn watch_replay(&mut self, player: ReplayPlayer) {
+        if is_logging_enabled() {
+            debug!("Entering replay mode with {} events", player.len());
+        }
+        self.replay_player = Some(player);
+        self.hint = None;
+    }
+
+    pub fn is_watching_replay(&self) -> bool {
+        self.replay_player.is_some()
     }
 
     pub fn render(
@@ -35,58 +107,387 @@ impl GameUI {
         game_state: &mut GameBoard,
         game_manager: &mut GameManager,
     ) {
+        if self.replay_player.is_some() {
+            self.render_replay(ui);
+            return;
+        }
+
         if game_state.game_started {
-            egui::TopBottomPanel::top("toolbar")
-                .frame(
-                    egui::Frame::default()
-                        .fill(if game_state.game_over && !game_state.game_won {
-                            egui::Color32::from_rgba_premultiplied(30, 32, 38, 200)
-                        } else if game_state.game_over && game_state.game_won {
-                            egui::Color32::from_rgba_premultiplied(34, 139, 34, 200)
+            self.welcome_screen.forget_save();
+            let can_edit = game_state.game_over || game_manager.timer.is_running();
+            let toolbar_action = Self::render_toolbar(
+                ui,
+                game_state,
+                game_manager.timer.get_elapsed(),
+                Some(can_edit),
+                &mut self.heatmap,
+            );
+
+            // Generation settled for a layout that may need guessing
+            if game_manager.no_guess && !game_state.no_guess && !game_state.first_click {
+                ui.label(
+                    egui::RichText::new(
+                        "⚠ No no-guess layout was found for this board, so it may need a guess.",
+                    )
+                    .color(crate::theme::Palette::default().danger),
+                );
+            }
+
+            // Reading the odds during play counts as help, like undo
+            if self.heatmap.is_enabled() && !game_state.game_over && !game_state.first_click {
+                game_state.assisted = true;
+            }
+
+            let input_enabled = !game_state.game_over && game_manager.timer.is_running();
+            let hint_key = input_enabled && ui.ctx().input(|i| i.key_pressed(egui::Key::H));
+            let history_action = match toolbar_action {
+                Some(ToolbarAction::Hint) => {
+                    self.show_hint(game_state, game_manager);
+                    None
+                }
+                Some(ToolbarAction::History(action)) => Some(action),
+                None => {
+                    if hint_key {
+                        self.show_hint(game_state, game_manager);
+                    }
+                    None
+                }
+            };
+
+            // A hint only stands until the board it explains changes
+            if matches!(&self.hint, Some((_, view)) if *view != BoardView::from_board(game_state)) {
+                self.hint = None;
+            }
+            if let Some((hint, _)) = &self.hint {
+                if Self::render_hint(ui, hint) {
+                    self.hint = None;
+                }
+            }
+
+            let cursor = self.hint.as_ref().map(|(hint, _)| hint.position());
+            let board_action = self.board_renderer.render(
+                ui,
+                game_state,
+                input_enabled,
+                cursor,
+                &mut self.heatmap,
+            );
+
+            if let Some(action) = history_action.or(board_action) {
+                game_manager.apply_action(game_state, action);
+            }
+
+            let watch_replay = if game_state.game_over && !game_state.game_won {
+                self.game_over_overlay.render(ui, game_state, game_manager)
+            } else if game_state.game_over && game_state.game_won {
+                self.win_overlay.render(ui, game_state, game_manager)
+            } else {
+                if game_state.game_started
+                    && !game_state.game_over
+                    && !game_manager.timer.is_running()
+                {
+                    self.pause_overlay.render(ui, game_state, game_manager);
+                }
+                false
+            };
+
+            if watch_replay {
+                if let Some(replay) = game_manager.replay() {
+                    match ReplayPlayer::new(replay.clone()) {
+                        Ok(player) => self.watch_replay(player),
+                        Err(e) => {
+                            if is_logging_enabled() {
+                                warn!("Could not load replay: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            self.welcome_screen
+                .render(ui, game_state, game_manager, &self.leaderboard);
+        }
+    }
+
+    fn show_hint(&mut self, game_state: &mut GameBoard, game_manager: &mut GameManager) {
+        self.hint = game_manager
+            .hint(game_state)
+            .map(|hint| (hint, BoardView::from_board(game_state)));
+    }
+
+    /// Explanation of the current hint under the board; returns true when dismissed.
+    fn render_hint(ui: &mut Ui, hint: &Hint) -> bool {
+        let mut dismiss = false;
+        egui::TopBottomPanel::bottom("hint")
+            .frame(
+                egui::Frame::default()
+                    .fill(egui::Color32::from_rgb(24, 26, 33))
+                    .inner_margin(egui::style::Margin::symmetric(16.0, 10.0)),
+            )
+            .show_inside(ui, |ui| {
+                let palette = crate::theme::Palette::default();
+                ui.horizontal(|ui| {
+                    let (icon, color) = match hint {
+                        Hint::Safe { .. } => ("💡", palette.success),
+                        Hint::Guess { .. } => ("🎲", palette.danger),
+                    };
+                    ui.label(egui::RichText::new(icon).color(color).size(16.0));
+                    ui.label(egui::RichText::new(hint.to_string()).color(palette.text));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("✖").on_hover_text("Dismiss").clicked() {
+                            dismiss = true;
+                        }
+                    });
+                });
+            });
+        dismiss
+    }
+
+    /// Top bar with title, time, mine counter, seed and the heatmap toggles. `history` is
+    /// `None` in replay mode; otherwise it enables the undo/redo and hint buttons, whose
+    /// click is returned.
+    fn render_toolbar(
+        ui: &mut Ui,
+        game_state: &GameBoard,
+        elapsed: Duration,
+        history: Option<bool>,
+        heatmap: &mut Heatmap,
+    ) -> Option<ToolbarAction> {
+        let mut action = None;
+        egui::TopBottomPanel::top("toolbar")
+            .frame(
+                egui::Frame::default()
+                    .fill(if game_state.game_over && !game_state.game_won {
+                        egui::Color32::from_rgba_premultiplied(30, 32, 38, 200)
+                    } else if game_state.game_over && game_state.game_won {
+                        egui::Color32::from_rgba_premultiplied(34, 139, 34, 200)
+                    } else {
+                        egui::Color32::from_rgb(24, 26, 33)
+                    })
+                    .rounding(egui::Rounding::same(0.0))
+                    .inner_margin(egui::style::Margin::symmetric(16.0, 12.0)),
+            )
+            .show_inside(ui, |ui| {
+                let palette = crate::theme::Palette::default();
+                ui.horizontal(|ui| {
+                    ui.heading(
+                        egui::RichText::new(if history.is_some() {
+                            "💣 Minesweeper"
                         } else {
-                            egui::Color32::from_rgb(24, 26, 33)
+                            "▶ Replay"
                         })
-                        .rounding(egui::Rounding::same(0.0))
-                        .inner_margin(egui::style::Margin::symmetric(16.0, 12.0)),
-                )
-                .show_inside(ui, |ui| {
-                    let palette = crate::theme::Palette::default();
-                    ui.horizontal(|ui| {
-                        ui.heading(
-                            egui::RichText::new("💣 Minesweeper")
+                        .color(palette.text)
+                        .strong()
+                        .size(24.0),
+                    );
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(
+                            egui::RichText::new(crate::utils::format_time(elapsed))
                                 .color(palette.text)
                                 .strong()
-                                .size(24.0),
+                                .size(18.0),
                         );
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label(
-                                egui::RichText::new(crate::utils::format_time(
-                                    game_manager.timer.get_elapsed(),
-                                ))
+                        ui.add_space(16.0);
+                        ui.label(
+                            egui::RichText::new(format!("🚩 {}", game_state.mines_remaining()))
                                 .color(palette.text)
                                 .strong()
                                 .size(18.0),
-                            );
-                        });
+                        )
+                        .on_hover_text(format!(
+                            "{} flags placed, {} mines",
+                            game_state.flags_placed(),
+                            game_state.mine_count
+                        ));
+                        ui.add_space(16.0);
+                        ui.label(
+                            egui::RichText::new(format!("Seed: {}", game_state.seed))
+                                .color(palette.text_muted)
+                                .size(14.0),
+                        );
+
+                        ui.add_space(16.0);
+                        if heatmap.is_enabled()
+                            && ui
+                                .selectable_label(heatmap.shows_percentages(), "%")
+                                .on_hover_text("Show mine chances as percentages")
+                                .clicked()
+                        {
+                            heatmap.toggle_percentages();
+                        }
+                        let heatmap_hint = if history.is_some() {
+                            "Mine probability heatmap - marks the game as assisted"
+                        } else {
+                            "Mine probability heatmap"
+                        };
+                        if ui
+                            .selectable_label(heatmap.is_enabled(), "📊")
+                            .on_hover_text(heatmap_hint)
+                            .clicked()
+                        {
+                            heatmap.toggle();
+                        }
+
+                        let Some(can_edit) = history else {
+                            return;
+                        };
+                        ui.add_space(16.0);
+                        if ui
+                            .add_enabled(
+                                can_edit && !game_state.game_over && !game_state.first_click,
+                                egui::Button::new("💡"),
+                            )
+                            .on_hover_text("Hint (H) - marks the game as assisted")
+                            .clicked()
+                        {
+                            action = Some(ToolbarAction::Hint);
+                        }
+                        if ui
+                            .add_enabled(
+                                can_edit && game_state.history().can_redo(),
+                                egui::Button::new("↷"),
+                            )
+                            .on_hover_text("Redo (Ctrl+Shift+Z)")
+                            .clicked()
+                        {
+                            action = Some(ToolbarAction::History(ReplayAction::Redo));
+                        }
+                        if ui
+                            .add_enabled(
+                                can_edit && game_state.history().can_undo(),
+                                egui::Button::new("↶"),
+                            )
+                            .on_hover_text("Undo (Ctrl+Z) - marks the game as assisted")
+                            .clicked()
+                        {
+                            action = Some(ToolbarAction::History(ReplayAction::Undo));
+                        }
                     });
                 });
+            });
+        action
+    }
 
-            self.board_renderer.render(ui, game_state, game_manager);
+    fn render_replay(&mut self, ui: &mut Ui) {
+        let Some(player) = &mut self.replay_player else {
+            return;
+        };
 
-            if game_state.game_over && !game_state.game_won {
-                self.game_over_overlay.render(ui, game_state, game_manager);
-            } else if game_state.game_over && game_state.game_won {
-                self.win_overlay.render(ui, game_state, game_manager);
-            } else if game_state.game_started
-                && !game_state.game_over
-                && !game_manager.timer.is_running()
-            {
-                self.pause_overlay.render(ui, game_state, game_manager);
+        let (dt, toggle, back, forward, close) = ui.ctx().input(|i| {
+            (
+                i.unstable_dt,
+                i.key_pressed(egui::Key::Space),
+                i.key_pressed(egui::Key::ArrowLeft),
+                i.key_pressed(egui::Key::ArrowRight),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        // Cap the step so a long stall between frames doesn't jump ahead
+        player.advance(Duration::from_secs_f32(dt.min(0.25)));
+        if toggle {
+            player.toggle_playing();
+        }
+        if back {
+            player.pause();
+            player.step_back();
+        }
+        if forward {
+            player.pause();
+            player.step_forward();
+        }
+
+        Self::render_toolbar(ui, player.board(), player.time(), None, &mut self.heatmap);
+        let close = Self::render_replay_controls(ui, player) || close;
+        self.board_renderer.render(
+            ui,
+            player.board(),
+            false,
+            player.cursor(),
+            &mut self.heatmap,
+        );
+
+        if player.is_playing() {
+            ui.ctx().request_repaint();
+        }
+        if close {
+            if is_logging_enabled() {
+                debug!("Leaving replay mode");
             }
-        } else {
-            self.welcome_screen.render(ui, game_state, game_manager);
+            self.replay_player = None;
         }
     }
+
+    /// Transport bar under the board; returns true when the viewer should close.
+    fn render_replay_controls(ui: &mut Ui, player: &mut ReplayPlayer) -> bool {
+        let mut close = false;
+        egui::TopBottomPanel::bottom("replay_controls")
+            .frame(
+                egui::Frame::default()
+                    .fill(egui::Color32::from_rgb(24, 26, 33))
+                    .inner_margin(egui::style::Margin::symmetric(16.0, 10.0)),
+            )
+            .show_inside(ui, |ui| {
+                let palette = crate::theme::Palette::default();
+                ui.horizontal(|ui| {
+                    if ui.button("⏮").on_hover_text("Step back (←)").clicked() {
+                        player.pause();
+                        player.step_back();
+                    }
+                    let play_label = if player.is_playing() { "⏸" } else { "▶" };
+                    if ui
+                        .button(play_label)
+                        .on_hover_text("Play/pause (Space)")
+                        .clicked()
+                    {
+                        player.toggle_playing();
+                    }
+                    if ui.button("⏭").on_hover_text("Step forward (→)").clicked() {
+                        player.pause();
+                        player.step_forward();
+                    }
+
+                    ui.add_space(8.0);
+                    let mut time_ms = player.time().as_millis() as u64;
+                    ui.spacing_mut().slider_width = 320.0;
+                    if ui
+                        .add(
+                            egui::Slider::new(&mut time_ms, 0..=player.duration_ms())
+                                .show_value(false),
+                        )
+                        .changed()
+                    {
+                        player.seek_time(time_ms);
+                    }
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} / {}  ·  move {}/{}",
+                            crate::utils::format_time(player.time()),
+                            crate::utils::format_time(Duration::from_millis(player.duration_ms())),
+                            player.position(),
+                            player.len()
+                        ))
+                        .color(palette.text_muted),
+                    );
+
+                    ui.add_space(8.0);
+                    for speed in REPLAY_SPEEDS {
+                        if ui
+                            .selectable_label(player.speed() == speed, format!("{}x", speed))
+                            .clicked()
+                        {
+                            player.set_speed(speed);
+                        }
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("✖ Close").on_hover_text("Esc").clicked() {
+                            close = true;
+                        }
+                    });
+                });
+            });
+        close
+    }
 }
 
 impl Default for GameUI {
@@ -1,18 +1,95 @@
-use crate::game::GameBoard;
-use crate::game_manager::{Difficulty, GameManager};
+use crate::records::Standing;
+use crate::replays::save_replay;
+use crate::saves::SaveFile;
 use crate::theme::Palette;
-use crate::utils::format_time;
+use crate::utils::{format_time, is_logging_enabled};
 use egui::{Color32, RichText, Ui};
-
-pub struct GameOverOverlay;
+use log::warn;
+use minesweeper_core::{Cell, Difficulty, GameBoard, GameManager, GameStats, Hint, PostMortem};
+
+pub struct GameOverOverlay {
+    /// Event count of the replay when it was saved, and where it went.
+    replay_status: Option<(usize, String)>,
+    /// Analysis of the lost board it was computed for.
+    post_mortem: Option<(Vec<Vec<Cell>>, Option<PostMortem>)>,
+}
 
 impl GameOverOverlay {
     pub fn new() -> Self {
-        Self
+        Self {
+            replay_status: None,
+            post_mortem: None,
+        }
     }
 
-    pub fn render(&self, ui: &mut Ui, game_state: &mut GameBoard, game_manager: &mut GameManager) {
+    /// The post-mortem for `game_state`, recomputed only when the board has changed.
+    fn post_mortem(&mut self, game_state: &GameBoard) -> Option<&PostMortem> {
+        if !matches!(&self.post_mortem, Some((board, _)) if *board == game_state.board) {
+            self.post_mortem = Some((game_state.board.clone(), game_state.post_mortem()));
+        }
+        self.post_mortem.as_ref().and_then(|(_, p)| p.as_ref())
+    }
+
+    /// Sentences explaining the loss, most important first.
+    fn analysis(post_mortem: Option<&PostMortem>) -> Vec<String> {
+        let Some(post_mortem) = post_mortem else {
+            return vec!["You hit a mine! Better luck next time.".to_string()];
+        };
+        let mut lines = Vec::new();
+        match &post_mortem.losing_click {
+            Some(click) => {
+                match &click.best_move {
+                    Some(Hint::Safe { .. }) if click.probability > 1.0 - 1e-9 => {
+                        lines.push("That cell was certainly a mine.".to_string())
+                    }
+                    Some(hint @ Hint::Safe { .. }) => {
+                        lines.push(format!("A safe move was available. {}", hint))
+                    }
+                    Some(Hint::Guess { x, y, probability })
+                        if *probability < click.probability - 1e-9 =>
+                    {
+                        lines.push(format!(
+                            "That was a forced guess, but ({}, {}) was safer at {:.0}%.",
+                            x,
+                            y,
+                            probability * 100.0
+                        ))
+                    }
+                    _ => lines.push(
+                        "That was a forced guess: nothing on the board was certain.".to_string(),
+                    ),
+                }
+                lines.push(format!(
+                    "The cell at ({}, {}) had a {:.0}% chance of a mine.",
+                    click.x,
+                    click.y,
+                    click.probability * 100.0
+                ));
+            }
+            None => lines.push("You hit a mine! Better luck next time.".to_string()),
+        }
+        match post_mortem.wrong_flags.len() {
+            0 => {}
+            1 => lines.push("1 flag was wrong - it is crossed out on the board.".to_string()),
+            n => lines.push(format!(
+                "{} flags were wrong - they are crossed out on the board.",
+                n
+            )),
+        }
+        lines
+    }
+
+    /// Returns true when the player asked to watch the replay of this game.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        game_state: &mut GameBoard,
+        game_manager: &mut GameManager,
+    ) -> bool {
         let palette = Palette::default();
+        let mut watch_replay = false;
+        let analysis = Self::analysis(self.post_mortem(game_state));
+        let stats = GameStats::new(game_state, game_manager.timer.get_elapsed());
 
         let screen_rect = ui.ctx().screen_rect();
         let painter = ui.painter();
@@ -23,7 +100,9 @@ impl GameOverOverlay {
         );
 
         let modal_width = 400.0;
-        let modal_height = 400.0;
+        // Room for the analysis, which wraps at roughly 45 characters a line
+        let analysis_lines: usize = analysis.iter().map(|line| line.len() / 45 + 1).sum();
+        let modal_height = 520.0 + 22.0 * analysis_lines as f32;
         let center_x = screen_rect.center().x;
         let center_y = screen_rect.center().y;
         let modal_rect = egui::Rect::from_center_size(
@@ -78,18 +157,19 @@ impl GameOverOverlay {
                             .size(16.0)
                             .color(palette.text_muted),
                         );
+                        stats_labels(ui, &palette, stats.as_ref());
                     });
                 });
 
                 ui.add_space(40.0);
 
-                ui.label(
-                    RichText::new("You hit a mine! Better luck next time.")
-                        .size(16.0)
-                        .color(palette.text),
-                );
+                for line in &analysis {
+                    ui.label(RichText::new(line).size(16.0).color(palette.text));
+                }
 
-                ui.add_space(20.0);
+                ui.add_space(10.0);
+                watch_replay = replay_controls(ui, &palette, game_manager, &mut self.replay_status);
+                ui.add_space(10.0);
 
                 ui.horizontal_centered(|ui| {
                     ui.add_space(20.0);
@@ -98,6 +178,7 @@ impl GameOverOverlay {
                         .min_size(egui::Vec2::new(160.0, 48.0));
 
                     if ui.add(play_again_button).clicked() {
+                        self.replay_status = None;
                         game_manager.reset_game(&mut *game_state);
                         game_manager.start_game(game_manager.current_difficulty);
                     }
@@ -108,6 +189,7 @@ impl GameOverOverlay {
                         .min_size(egui::Vec2::new(160.0, 48.0));
 
                     if ui.add(main_menu_button).clicked() {
+                        self.replay_status = None;
                         game_state.reset();
                         game_manager.pause_game();
                     }
@@ -118,6 +200,8 @@ impl GameOverOverlay {
                 ui.add_space(20.0);
             });
         });
+
+        watch_replay
     }
 
     fn primary_button(
@@ -135,12 +219,8 @@ impl GameOverOverlay {
             .stroke(egui::Stroke::new(0.0, Color32::TRANSPARENT))
     }
 
-    fn get_difficulty_name(&self, difficulty: Difficulty) -> &'static str {
-        match difficulty {
-            Difficulty::Beginner => "Beginner",
-            Difficulty::Intermediate => "Intermediate",
-            Difficulty::Expert => "Expert",
-        }
+    fn get_difficulty_name(&self, difficulty: Difficulty) -> String {
+        difficulty.name()
     }
 }
 
@@ -163,7 +243,7 @@ impl PauseOverlay {
         );
 
         let modal_width = 300.0;
-        let modal_height = 280.0;
+        let modal_height = 340.0;
         let center_x = screen_rect.center().x;
         let center_y = screen_rect.center().y;
         let modal_rect = egui::Rect::from_center_size(
@@ -201,6 +281,29 @@ impl PauseOverlay {
                     }
                     ui.add_space(12.0);
 
+                    let save_and_quit = Self::primary_button("💾 Save & Quit", palette.accent_soft)
+                        .min_size(egui::Vec2::new(200.0, 48.0));
+                    if ui
+                        .add(save_and_quit)
+                        .on_hover_text("Continue it later from the main menu")
+                        .clicked()
+                    {
+                        if let Some(saved) = game_manager.save_game(game_state) {
+                            match SaveFile::save(&saved) {
+                                Ok(()) => {
+                                    game_state.reset();
+                                    game_manager.pause_game();
+                                }
+                                Err(e) => {
+                                    if is_logging_enabled() {
+                                        warn!("Could not save game: {}", e);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    ui.add_space(12.0);
+
                     let end_game = Self::primary_button("🏁 End Game", palette.danger)
                         .min_size(egui::Vec2::new(200.0, 48.0));
                     if ui.add(end_game).clicked() {
@@ -229,15 +332,36 @@ impl PauseOverlay {
     }
 }
 
-pub struct WinOverlay;
+pub struct WinOverlay {
+    /// Event count of the replay when it was saved, and where it went.
+    replay_status: Option<(usize, String)>,
+    /// How the game placed on the leaderboard, if it was recorded.
+    standing: Option<Standing>,
+}
 
 impl WinOverlay {
     pub fn new() -> Self {
-        Self
+        Self {
+            replay_status: None,
+            standing: None,
+        }
     }
 
-    pub fn render(&self, ui: &mut Ui, game_state: &mut GameBoard, game_manager: &mut GameManager) {
+    pub fn set_standing(&mut self, standing: Option<Standing>) {
+        self.standing = standing;
+    }
+
+    /// Returns true when the player asked to watch the replay of this game.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        game_state: &mut GameBoard,
+        game_manager: &mut GameManager,
+    ) -> bool {
         let palette = Palette::default();
+        let mut watch_replay = false;
+        let difficulty_name = self.get_difficulty_name(game_manager.current_difficulty);
+        let stats = GameStats::new(game_state, game_manager.timer.get_elapsed());
 
         let screen_rect = ui.ctx().screen_rect();
         let painter = ui.painter();
@@ -248,7 +372,7 @@ impl WinOverlay {
         );
 
         let modal_width = 400.0;
-        let modal_height = 400.0;
+        let modal_height = 570.0;
         let center_x = screen_rect.center().x;
         let center_y = screen_rect.center().y;
         let modal_rect = egui::Rect::from_center_size(
@@ -296,13 +420,11 @@ impl WinOverlay {
                         );
                         ui.add_space(5.0);
                         ui.label(
-                            RichText::new(format!(
-                                "Difficulty: {}",
-                                self.get_difficulty_name(game_manager.current_difficulty)
-                            ))
-                            .size(16.0)
-                            .color(palette.text_muted),
+                            RichText::new(format!("Difficulty: {}", difficulty_name))
+                                .size(16.0)
+                                .color(palette.text_muted),
                         );
+                        stats_labels(ui, &palette, stats.as_ref());
                     });
                 });
 
@@ -314,7 +436,39 @@ impl WinOverlay {
                         .color(palette.text),
                 );
 
-                ui.add_space(20.0);
+                if let Some(Standing {
+                    rank: Some(rank),
+                    personal_best,
+                }) = self.standing
+                {
+                    let place = format!("#{} on the {} leaderboard", rank + 1, difficulty_name);
+                    if personal_best {
+                        ui.label(
+                            RichText::new(format!("🏆 New personal best - {}", place))
+                                .size(16.0)
+                                .color(palette.success)
+                                .strong(),
+                        );
+                    } else {
+                        ui.label(RichText::new(place).size(14.0).color(palette.text_muted));
+                    }
+                }
+
+                if game_state.assisted {
+                    let text = match game_state.hints_used {
+                        0 => "Assisted game - not counted toward best times".to_string(),
+                        1 => "Assisted game (1 hint) - not counted toward best times".to_string(),
+                        n => format!(
+                            "Assisted game ({} hints) - not counted toward best times",
+                            n
+                        ),
+                    };
+                    ui.label(RichText::new(text).size(14.0).color(palette.text_muted));
+                }
+
+                ui.add_space(10.0);
+                watch_replay = replay_controls(ui, &palette, game_manager, &mut self.replay_status);
+                ui.add_space(10.0);
 
                 ui.horizontal_centered(|ui| {
                     ui.add_space(20.0);
@@ -323,6 +477,8 @@ impl WinOverlay {
                         .min_size(egui::Vec2::new(160.0, 48.0));
 
                     if ui.add(play_again_button).clicked() {
+                        self.replay_status = None;
+                        self.standing = None;
                         game_manager.reset_game(&mut *game_state);
                         game_manager.start_game(game_manager.current_difficulty);
                     }
@@ -333,6 +489,8 @@ impl WinOverlay {
                         .min_size(egui::Vec2::new(160.0, 48.0));
 
                     if ui.add(main_menu_button).clicked() {
+                        self.replay_status = None;
+                        self.standing = None;
                         game_state.reset();
                         game_manager.pause_game();
                     }
@@ -343,6 +501,8 @@ impl WinOverlay {
                 ui.add_space(20.0);
             });
         });
+
+        watch_replay
     }
 
     fn primary_button(
@@ -360,11 +520,89 @@ impl WinOverlay {
             .stroke(egui::Stroke::new(0.0, Color32::TRANSPARENT))
     }
 
-    fn get_difficulty_name(&self, difficulty: Difficulty) -> &'static str {
-        match difficulty {
-            Difficulty::Beginner => "Beginner",
-            Difficulty::Intermediate => "Intermediate",
-            Difficulty::Expert => "Expert",
-        }
+    fn get_difficulty_name(&self, difficulty: Difficulty) -> String {
+        difficulty.name()
+    }
+}
+
+/// 3BV, speed and click efficiency under the score. A lost game shows how much of the
+/// 3BV was solved before the mine.
+fn stats_labels(ui: &mut Ui, palette: &Palette, stats: Option<&GameStats>) {
+    let Some(stats) = stats else {
+        return;
+    };
+    let three_bv = if stats.three_bv_solved == stats.three_bv {
+        format!("3BV: {}", stats.three_bv)
+    } else {
+        format!("3BV solved: {}/{}", stats.three_bv_solved, stats.three_bv)
+    };
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    let clicks = stats.clicks;
+    let lines = [
+        format!(
+            "{}  |  3BV/s: {}",
+            three_bv,
+            or_dash(stats.three_bv_per_second().map(|r| format!("{:.2}", r)))
+        ),
+        format!(
+            "IOE: {}  |  Correctness: {}",
+            or_dash(stats.ioe().map(|r| format!("{:.2}", r))),
+            or_dash(stats.correctness().map(|r| format!("{:.0}%", r * 100.0)))
+        ),
+        format!(
+            "Clicks: {} left, {} right, {} chord",
+            clicks.left, clicks.right, clicks.chord
+        ),
+    ];
+
+    ui.add_space(5.0);
+    for line in lines {
+        ui.label(RichText::new(line).size(14.0).color(palette.text_muted));
+    }
+}
+
+/// "Watch Replay" and "Save Replay" buttons for a finished game, followed by where the
+/// replay was saved. Returns true when "Watch Replay" was clicked.
+fn replay_controls(
+    ui: &mut Ui,
+    palette: &Palette,
+    game_manager: &GameManager,
+    status: &mut Option<(usize, String)>,
+) -> bool {
+    let Some(replay) = game_manager.replay() else {
+        return false;
+    };
+    // An undo reopens the game and adds events, so an earlier save no longer covers it
+    if status
+        .as_ref()
+        .is_some_and(|(events, _)| *events != replay.events.len())
+    {
+        *status = None;
+    }
+
+    let watch = ui.button("▶ Watch Replay").clicked();
+    if ui
+        .add_enabled(status.is_none(), egui::Button::new("💾 Save Replay"))
+        .clicked()
+    {
+        let message = match save_replay(replay) {
+            Ok(path) => format!("Saved to {}", path.display()),
+            Err(e) => {
+                if is_logging_enabled() {
+                    warn!("Could not save replay: {}", e);
+                }
+                format!("Could not save replay: {}", e)
+            }
+        };
+        *status = Some((replay.events.len(), message));
+    }
+
+    if let Some((_, message)) = status {
+        ui.label(
+            RichText::new(message.as_str())
+                .size(12.0)
+                .color(palette.text_muted),
+        );
     }
+    watch
 }
@@ -1,17 +1,58 @@
-use crate::game::GameBoard;
-use crate::game_manager::{Difficulty, GameManager};
+use crate::records::Leaderboard;
+use crate::saves::SaveFile;
+use crate::settings::Settings;
 use crate::theme::Palette;
+use crate::utils::{format_date, is_logging_enabled};
 use egui::{Color32, RichText, Ui};
+use log::warn;
+use minesweeper_core::game::no_guess::is_no_guess_practical;
+use minesweeper_core::game_manager::difficulty::{MAX_CUSTOM_DIMENSION, MIN_CUSTOM_DIMENSION};
+use minesweeper_core::{Difficulty, GameBoard, GameManager};
 
-pub struct WelcomeScreen;
+pub struct WelcomeScreen {
+    custom_width: usize,
+    custom_height: usize,
+    custom_mines: usize,
+    custom_error: Option<String>,
+    continue_error: Option<String>,
+    /// Whether a saved game exists, checked once each time the screen is shown.
+    has_save: Option<bool>,
+    player_name: String,
+}
 
 impl WelcomeScreen {
     pub fn new() -> Self {
-        Self
+        Self {
+            custom_width: 20,
+            custom_height: 16,
+            custom_mines: 50,
+            custom_error: None,
+            continue_error: None,
+            has_save: None,
+            player_name: Settings::load().player_name,
+        }
     }
 
-    pub fn render(&self, ui: &mut Ui, game_state: &mut GameBoard, game_manager: &mut GameManager) {
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        game_state: &mut GameBoard,
+        game_manager: &mut GameManager,
+        leaderboard: &Leaderboard,
+    ) {
         let palette = Palette::default();
+        egui::SidePanel::right("leaderboard")
+            .resizable(false)
+            .exact_width(360.0)
+            .frame(
+                egui::Frame::default()
+                    .fill(palette.surface_1)
+                    .inner_margin(egui::style::Margin::same(16.0)),
+            )
+            .show_inside(ui, |ui| {
+                self.render_leaderboard(ui, leaderboard, game_manager.current_difficulty);
+            });
+
         ui.allocate_ui_with_layout(
             egui::Vec2::new(ui.available_width(), ui.available_height()),
             egui::Layout::top_down(egui::Align::Center),
@@ -125,20 +166,163 @@ impl WelcomeScreen {
                                 }
                             });
 
+                            ui.add_space(12.0);
+                            self.render_custom_panel(ui, game_state, game_manager);
+
+                            ui.add_space(16.0);
+                            let mut no_guess = game_manager.no_guess;
+                            if ui
+                                .checkbox(
+                                    &mut no_guess,
+                                    RichText::new("No-guess boards (solvable by logic alone)")
+                                        .size(16.0)
+                                        .color(palette.text),
+                                )
+                                .changed()
+                            {
+                                game_manager.no_guess = no_guess;
+                                *game_state = game_manager.create_board();
+                            }
+                            let (width, height, mines) =
+                                game_manager.current_difficulty.get_dimensions();
+                            if game_manager.no_guess && !is_no_guess_practical(width, height, mines)
+                            {
+                                ui.label(
+                                    RichText::new(
+                                        "⚠ This board is too large or dense for reliable no-guess \
+                                         layouts; most will need a guess.",
+                                    )
+                                    .size(14.0)
+                                    .color(palette.danger),
+                                );
+                            }
+
+                            let mut question_marks = game_manager.question_marks;
+                            if ui
+                                .checkbox(
+                                    &mut question_marks,
+                                    RichText::new("Question marks (right-click: 🚩 → ❓ → blank)")
+                                        .size(16.0)
+                                        .color(palette.text),
+                                )
+                                .changed()
+                            {
+                                game_manager.question_marks = question_marks;
+                                game_state.question_marks = question_marks;
+                                Self::save_question_marks(question_marks);
+                            }
+
                             ui.add_space(24.0);
                             let start_button =
                                 Self::primary_button("🎮 Start New Game", palette.success)
                                     .min_size(egui::Vec2::new(220.0, 48.0));
                             if ui.add(start_button).clicked() {
+                                game_manager.reset_game(game_state);
                                 game_state.start_game();
                                 game_manager.start_game(game_manager.current_difficulty);
                             }
+
+                            if *self.has_save.get_or_insert_with(SaveFile::exists) {
+                                ui.add_space(12.0);
+                                let continue_button =
+                                    Self::primary_button("⏯ Continue Last Game", palette.accent)
+                                        .min_size(egui::Vec2::new(220.0, 40.0));
+                                if ui.add(continue_button).clicked() {
+                                    self.continue_saved_game(game_state, game_manager);
+                                }
+                            }
+                            if let Some(error) = &self.continue_error {
+                                ui.label(RichText::new(error).size(14.0).color(palette.danger));
+                            }
                         });
                     });
             },
         );
     }
 
+    /// The best times for `difficulty`, and the name new ones are recorded under.
+    fn render_leaderboard(
+        &mut self,
+        ui: &mut Ui,
+        leaderboard: &Leaderboard,
+        difficulty: Difficulty,
+    ) {
+        let palette = Palette::default();
+        ui.label(
+            RichText::new(format!("🏆 Best times: {}", difficulty.name()))
+                .size(18.0)
+                .color(palette.text)
+                .strong(),
+        );
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Player").color(palette.text_muted));
+            let name_field = egui::TextEdit::singleline(&mut self.player_name)
+                .hint_text("Player")
+                .desired_width(160.0);
+            if ui.add(name_field).changed() {
+                Self::save_player_name(&self.player_name);
+            }
+        });
+        ui.add_space(8.0);
+
+        let records = leaderboard.records(difficulty);
+        if records.is_empty() {
+            ui.label(
+                RichText::new("No wins yet. Assisted games don't count.")
+                    .size(14.0)
+                    .color(palette.text_muted),
+            );
+            return;
+        }
+        egui::Grid::new("leaderboard_records")
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for heading in ["", "Time", "3BV/s", "Name", "Date"] {
+                    ui.label(RichText::new(heading).size(12.0).color(palette.text_muted));
+                }
+                ui.end_row();
+                for (rank, record) in records.iter().enumerate() {
+                    let rate = record
+                        .three_bv_per_second
+                        .map_or("-".to_string(), |rate| format!("{:.2}", rate));
+                    ui.label(RichText::new(format!("{}.", rank + 1)).color(palette.text_muted));
+                    ui.label(
+                        RichText::new(format!("{:.2} s", record.time_ms as f64 / 1000.0))
+                            .color(palette.accent),
+                    );
+                    ui.label(RichText::new(rate).color(palette.text_muted));
+                    ui.label(RichText::new(&record.name).color(palette.text));
+                    ui.label(RichText::new(format_date(record.date)).color(palette.text_muted));
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Makes the next render check for a saved game again. Called while the screen is
+    /// hidden, since a game may be saved before it comes back.
+    pub fn forget_save(&mut self) {
+        self.has_save = None;
+    }
+
+    /// Resumes the saved game paused and removes the save so it is only continued once.
+    /// A save that can't be loaded is kept.
+    fn continue_saved_game(&mut self, game_state: &mut GameBoard, game_manager: &mut GameManager) {
+        match SaveFile::load() {
+            Some(saved) => {
+                self.continue_error = None;
+                *game_state = game_manager.restore_game(saved);
+                SaveFile::delete();
+                self.has_save = Some(false);
+            }
+            None => {
+                self.continue_error = Some("The saved game could not be loaded.".to_string());
+            }
+        }
+    }
+
     fn primary_button(
         label: impl Into<egui::WidgetText> + 'static,
         fill: Color32,
@@ -162,14 +346,89 @@ impl WelcomeScreen {
             .stroke(egui::Stroke::new(0.0, Color32::TRANSPARENT))
     }
 
+    fn render_custom_panel(
+        &mut self,
+        ui: &mut Ui,
+        game_state: &mut GameBoard,
+        game_manager: &mut GameManager,
+    ) {
+        let palette = Palette::default();
+        let custom_selected = matches!(game_manager.current_difficulty, Difficulty::Custom { .. });
+        let dimension_range = MIN_CUSTOM_DIMENSION..=MAX_CUSTOM_DIMENSION;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Custom").size(16.0).color(palette.text));
+            ui.add(
+                egui::DragValue::new(&mut self.custom_width)
+                    .clamp_range(dimension_range.clone())
+                    .prefix("W "),
+            );
+            ui.label(RichText::new("×").color(palette.text_muted));
+            ui.add(
+                egui::DragValue::new(&mut self.custom_height)
+                    .clamp_range(dimension_range)
+                    .prefix("H "),
+            );
+            ui.add(
+                egui::DragValue::new(&mut self.custom_mines)
+                    .clamp_range(1..=MAX_CUSTOM_DIMENSION * MAX_CUSTOM_DIMENSION)
+                    .suffix(" mines"),
+            );
+
+            let mut custom_button = Self::primary_button(
+                "Use custom",
+                if custom_selected {
+                    palette.accent
+                } else {
+                    palette.surface_2
+                },
+            );
+            if custom_selected {
+                custom_button = custom_button.stroke(egui::Stroke::new(1.0, palette.accent_soft));
+            }
+            if ui.add(custom_button).clicked() {
+                match Difficulty::custom(self.custom_width, self.custom_height, self.custom_mines) {
+                    Ok(difficulty) => {
+                        self.custom_error = None;
+                        self.change_difficulty(game_state, game_manager, difficulty);
+                    }
+                    Err(error) => self.custom_error = Some(error.to_string()),
+                }
+            }
+        });
+
+        if let Some(error) = &self.custom_error {
+            ui.label(RichText::new(error).size(14.0).color(palette.danger));
+        }
+    }
+
+    fn save_question_marks(question_marks: bool) {
+        let mut settings = Settings::load();
+        settings.question_marks = question_marks;
+        if let Err(e) = settings.save() {
+            if is_logging_enabled() {
+                warn!("Could not save settings: {}", e);
+            }
+        }
+    }
+
+    fn save_player_name(player_name: &str) {
+        let mut settings = Settings::load();
+        settings.player_name = player_name.trim().to_string();
+        if let Err(e) = settings.save() {
+            if is_logging_enabled() {
+                warn!("Could not save settings: {}", e);
+            }
+        }
+    }
+
     fn change_difficulty(
         &self,
         game_state: &mut GameBoard,
         game_manager: &mut GameManager,
         difficulty: Difficulty,
     ) {
-        let (width, height, mines) = difficulty.get_dimensions();
-        *game_state = GameBoard::new(width, height, mines);
         game_manager.current_difficulty = difficulty;
+        *game_state = game_manager.create_board();
     }
 }
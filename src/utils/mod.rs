@@ -1,7 +1,8 @@
-pub mod debug;
+pub mod paths;
 pub mod time;
 
-pub use debug::{
+pub use minesweeper_core::debug::{
     is_logging_enabled, is_show_mines_enabled, set_debug_enabled, set_show_mines_enabled,
 };
-pub use time::format_time;
+pub use paths::{config_dir, data_dir, write_atomically};
+pub use time::{format_date, format_time};
@@ -0,0 +1,47 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+const APP_DIR: &str = "minesweeper";
+
+/// `$XDG_CONFIG_HOME/minesweeper`, falling back to `~/.config/minesweeper`.
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_DATA_HOME/minesweeper`, falling back to `~/.local/share/minesweeper`.
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// Writes a temporary file next to `path` and renames it into place, so a crash leaves
+/// either the old contents or the new ones, never half a file. Creates the parent
+/// directory if needed.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(format!(".{}.tmp", process::id()));
+    let temp_path = PathBuf::from(temp_name);
+
+    let written = fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn xdg_dir(variable: &str, home_fallback: &str) -> Option<PathBuf> {
+    let base = match env::var_os(variable) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(home_fallback),
+    };
+    Some(base.join(APP_DIR))
+}
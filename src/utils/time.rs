@@ -7,6 +7,26 @@ pub fn format_time(duration: Duration) -> String {
     format!("{:02}:{:02}", minutes, seconds)
 }
 
+/// The UTC calendar date of `unix_seconds` as `YYYY-MM-DD`.
+pub fn format_date(unix_seconds: u64) -> String {
+    // Days to civil date, after Howard Hinnant's algorithm with eras of 400 years
+    let days = unix_seconds / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +80,11 @@ mod tests {
         assert_eq!(format_time(Duration::from_secs(61)), "01:01");
         assert_eq!(format_time(Duration::from_secs(999)), "16:39");
     }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_760_659_200), "2025-10-17");
+    }
 }